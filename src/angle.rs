@@ -1,71 +1,133 @@
 //! Some people prefer giving angles in radians while others prefer degrees.
-//! This type solves this problem by containing both radians and degrees, while
-//! letting the user decide which preference they have.
+//! This type solves this problem by accepting and returning both radians and degrees, while
+//! letting the user decide which preference they have. Internally, only radians are stored.
 //!
 //! `Angle::new(angle: f32)` will take in radians by default.
 //! You can change this to use degrees by default by enabling the `"angle_new_degrees"` feature
 
 use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
 
 #[cfg(not(feature = "use_f64"))]
-use std::f32::consts::{PI, TAU};
+use core::f32::consts::{PI, TAU};
 
 #[cfg(feature = "use_f64")]
-use std::f64::consts::{PI, TAU};
+use core::f64::consts::{PI, TAU};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// Positive angles are counter-clockwise (ccw)
-
 #[repr(C)]
 #[derive(
     Debug, Clone, Copy, PartialEq, PartialOrd, bytemuck::Pod, bytemuck::Zeroable,
     derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
-    derive_more::Rem, derive_more::RemAssign, derive_more::Neg
+    derive_more::Neg
 )]
 pub struct Angle
 {
     rad: Scalar,
-    deg: Scalar,
 }
 
-impl std::fmt::Display for Angle
+/// Write `value` with the precision of `f`, if it has one.
+fn write_value(f: &mut core::fmt::Formatter<'_>, value: Scalar) -> core::fmt::Result
+{
+    match f.precision()
+    {
+        Some(precision) => write!(f, "{:.*}", precision, value),
+        None => write!(f, "{}", value),
+    }
+}
+
+/// Shows both degrees and radians, e.g. `Angle { 90° / 1.5707964 rad }`.
+/// The alternate form (`{:#}`) only shows degrees, e.g. `90°`. A precision (e.g. `{:.2}`) is applied to all numbers.
+/// Use `Angle::display()` to choose the unit.
+impl core::fmt::Display for Angle
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            return self.display(AngleUnit::Degrees).fmt(f)
+        }
+
+        write!(f, "Angle {{ ")?;
+        write_value(f, self.deg())?;
+        write!(f, "° / ")?;
+        write_value(f, self.rad)?;
+        write!(f, " rad }}")
+    }
+}
+
+/// Units angles can be shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AngleUnit
+{
+    Degrees,
+    Radians,
+}
+
+/// Shows an angle in a single unit, see `Angle::display()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AngleDisplay
+{
+    angle: Angle,
+    unit:  AngleUnit,
+}
+
+impl core::fmt::Display for AngleDisplay
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Angle {{ {}° / {}π }}", self.deg, self.rad)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.unit
+        {
+            AngleUnit::Degrees => { write_value(f, self.angle.deg())?; write!(f, "°") }
+            AngleUnit::Radians => { write_value(f, self.angle.rad())?; write!(f, " rad") }
+        }
     }
 }
 
 auto_ops::impl_op_ex_commutative!(* |lhs: &Angle, rhs: &Scalar| -> Angle {
-    Angle { rad: lhs.rad * rhs, deg: lhs.deg* rhs }
+    Angle { rad: lhs.rad * rhs }
 });
 auto_ops::impl_op_ex!(*= |lhs: &mut Angle, rhs: &Scalar| {
     lhs.rad *= rhs;
-    lhs.deg *= rhs;
 });
 
 auto_ops::impl_op_ex!(/ |lhs: &Angle, rhs: &Scalar| -> Angle {
-    Angle { rad: lhs.rad / rhs, deg: lhs.deg / rhs }
+    Angle { rad: lhs.rad / rhs }
 });
 auto_ops::impl_op_ex!(/= |lhs: &mut Angle, rhs: &Scalar| {
     lhs.rad /= rhs;
-    lhs.deg /= rhs;
+});
+
+// Computed in degrees, so e.g. 100° % 30° is exactly 10°
+auto_ops::impl_op_ex!(% |lhs: &Angle, rhs: &Angle| -> Angle {
+    Angle::degrees(lhs.deg() % rhs.deg())
+});
+auto_ops::impl_op_ex!(%= |lhs: &mut Angle, rhs: &Angle| {
+    *lhs = *lhs % rhs;
+});
+
+// The ratio of two angles, e.g. how often one fits into the other
+auto_ops::impl_op_ex!(/ |lhs: &Angle, rhs: &Angle| -> Scalar {
+    lhs.rad / rhs.rad
 });
 
 impl Angle
 {
     /// 360°
-    pub const FULL   : Angle = Angle { rad: TAU,    deg: 360.0 };
+    pub const FULL   : Angle = Angle { rad: TAU };
 
     /// 180°
-    pub const HALF   : Angle = Angle { rad: PI,     deg: 180.0 };
+    pub const HALF   : Angle = Angle { rad: PI };
 
     /// 90°
-    pub const QUARTER: Angle = Angle { rad: PI/2.0, deg:  90.0 };
+    pub const QUARTER: Angle = Angle { rad: PI/2.0 };
 
     /// 45°
-    pub const EIGTH:   Angle = Angle { rad: PI/4.0, deg:  45.0 };
+    pub const EIGTH:   Angle = Angle { rad: PI/4.0 };
 
     /// 0°
-    pub const ZERO   : Angle = Angle { rad: 0.0,    deg:   0.0 };
+    pub const ZERO   : Angle = Angle { rad: 0.0 };
 
     /// Create Angle from degrees
     #[cfg(feature = "angle_new_degrees")]
@@ -84,13 +146,57 @@ impl Angle
     /// Create Angle from radians
     pub fn radians(angle: Scalar) -> Self
     {
-        Self { rad: angle, deg: angle*(180.0/PI) }
+        Self { rad: angle }
     }
 
     /// Create Angle from degrees
     pub fn degrees(angle: Scalar) -> Self
     {
-        Self { deg: angle, rad: angle*(PI/180.0) }
+        Self { rad: angle.to_radians() }
+    }
+
+    /// Angle of the point (x, y) measured ccw from the positive x-axis, in (-180°,180°].
+    pub fn atan2(y: Scalar, x: Scalar) -> Self
+    {
+        Self::radians(y.atan2(x))
+    }
+
+    /// Angle with the given sine and cosine, in (-180°,180°].
+    /// They don't have to be normalized, e.g. `(r·sin, r·cos)` works as well.
+    pub fn from_sin_cos(sin: Scalar, cos: Scalar) -> Self
+    {
+        Self::atan2(sin, cos)
+    }
+
+    /// Arcsine in [-90°,90°]. `x` is clamped into [-1,1] first, so rounding errors
+    /// (e.g. of dot products of normalized vectors) don't produce NaN.
+    pub fn asin(x: Scalar) -> Self
+    {
+        Self::radians(x.clamp(-1.0, 1.0).asin())
+    }
+
+    /// Arccosine in [0°,180°]. `x` is clamped into [-1,1] first, see `Angle::asin()`.
+    pub fn acos(x: Scalar) -> Self
+    {
+        Self::radians(x.clamp(-1.0, 1.0).acos())
+    }
+
+    /// Arctangent in (-90°,90°).
+    pub fn atan(x: Scalar) -> Self
+    {
+        Self::radians(x.atan())
+    }
+
+    /// Arcsine in [-90°,90°]. Returns `None` if `x` isn't in [-1,1].
+    pub fn asin_strict(x: Scalar) -> Option<Self>
+    {
+        (-1.0..=1.0).contains(&x).then(|| Self::radians(x.asin()))
+    }
+
+    /// Arccosine in [0°,180°]. Returns `None` if `x` isn't in [-1,1].
+    pub fn acos_strict(x: Scalar) -> Option<Self>
+    {
+        (-1.0..=1.0).contains(&x).then(|| Self::radians(x.acos()))
     }
 
     /// Get this angle in radians
@@ -99,102 +205,165 @@ impl Angle
         self.rad
     }
 
-    /// Get this angle in degrees
+    /// Get this angle in degrees.
+    /// Angles created from (nearly) round degrees give them back exactly, e.g. 60 instead of 60.000004.
     pub fn deg(&self) -> Scalar
     {
-        self.deg
+        let deg = self.rad.to_degrees();
+
+        // Take the roundest number of degrees which converts to the same radians
+        for scale in [1.0, 10.0, 100.0, 1000.0]
+        {
+            let rounded = (deg * scale).round() / scale;
+
+            if rounded.to_radians() == self.rad {
+                return rounded
+            }
+        }
+
+        deg
+    }
+
+    /// Rotation rate of this angle per second, e.g. `Angle::degrees(90.0).per_second() * frame_time`.
+    pub fn per_second(&self) -> AngularRate
+    {
+        AngularRate { rad_per_sec: self.rad }
+    }
+
+    /// Show this angle only in `unit`, e.g. `format!("{:.1}", angle.display(AngleUnit::Radians))` gives `1.6 rad`.
+    pub fn display(&self, unit: AngleUnit) -> AngleDisplay
+    {
+        AngleDisplay { angle: *self, unit }
     }
 
     /// Minimum of two angles
     pub fn min(&self, angle: Angle) -> Angle
     {
-        if self.deg < angle.deg { *self } else { angle }
+        if self.rad < angle.rad { *self } else { angle }
     }
 
     /// Maximum of two angles
     pub fn max(&self, angle: Angle) -> Angle
     {
-        if self.deg > angle.deg { *self } else { angle }
+        if self.rad > angle.rad { *self } else { angle }
     }
 
     /// Clamp this Angle between two angles
     pub fn clamp(&self, min: Angle, max: Angle) -> Angle
     {
-        if self.deg > max.deg { max }
-        else if self.deg < min.deg { min }
+        if self.rad > max.rad { max }
+        else if self.rad < min.rad { min }
         else { *self }
     }
 
     /// Ignore sign of angle.
     pub fn abs(&self) -> Self
     {
-        Self { rad: self.rad.abs(), deg: self.deg.abs() }
+        Self { rad: self.rad.abs() }
     }
 
     pub fn signum(&self) -> Scalar
     {
-        self.deg.signum()
+        self.rad.signum()
+    }
+
+    /// How many times `angle` fits into this Angle, snapped to whole numbers within rounding errors.
+    fn steps(&self, angle: Angle) -> Scalar
+    {
+        let a = self.deg() / angle.deg();
+        let n = a.round();
+
+        if (a - n).abs() <= 1e-5 * n.abs().max(1.0) { n } else { a }
+    }
+
+    /// `n` times `angle`, computed in degrees so multiples of round degrees stay exact.
+    fn multiple(angle: Angle, n: Scalar) -> Self
+    {
+        Self::degrees(angle.deg() * n)
     }
 
     /// Round to the nearest smaller multiple of some Angle
     pub fn floor(&self, angle: Angle) -> Self
     {
-        let n = (self.deg / angle.deg).trunc();
+        let n = self.steps(angle).trunc();
 
-        angle * n
+        Self::multiple(angle, n)
     }
 
     /// Round to the nearest greater multiple of some Angle
     pub fn ceil(&self, angle: Angle) -> Self
     {
-        let a = self.deg / angle.deg;
+        let a = self.steps(angle);
         let n = if a < 0.0 {
             a.floor()
         } else {
             a.ceil()
         };
 
-        angle * n
+        Self::multiple(angle, n)
     }
 
     /// Round to the nearest multiple of some Angle
     pub fn round(&self, angle: Angle) -> Self
     {
-        let n = (self.deg / angle.deg).round();
-        angle * n
+        let n = self.steps(angle).round();
+        Self::multiple(angle, n)
+    }
+
+    /// How many whole times `angle` fits into this Angle, rounded towards negative infinity for positive `angle`.
+    /// E.g. 100° fits 3 detents of 30°, and -100° gives -4.
+    pub fn div_euclid(&self, angle: Angle) -> Scalar
+    {
+        self.rad.div_euclid(angle.rad)
+    }
+
+    /// What's left after `div_euclid()`, always in [0,|angle|).
+    /// E.g. 100° by 30° leaves 10°, and -100° leaves 20°.
+    pub fn rem_euclid(&self, angle: Angle) -> Self
+    {
+        Self::radians(self.rad.rem_euclid(angle.rad))
     }
 
     /// Will this Angle to its positive value in [0°,360°).
     /// If this Angle would be -20°, then this method will return 340°
     pub fn corrected(&self) -> Self
     {
-        let modulo = self.deg % 360.0;
-        let angle  = if self.deg < 0.0 { 360.0 + modulo } else { modulo };
-        Self::radians(angle)
+        // rem_euclid rounds tiny negative angles up to exactly 360°
+        let angle = self.rad.rem_euclid(TAU);
+        Self::radians(if angle >= TAU { 0.0 } else { angle })
     }
 
     /// Will convert this Angle to its positive value without clamping to [0°,360°).
     /// If this Angle would be -380°, then this method will return 700°
     pub fn sign_corrected(&self) -> Self
     {
-        let angle = if self.deg < 0.0
+        let angle = if self.rad < 0.0
         {
-            let n_deg = (self.deg / 360.0).floor() * -360.0;
-            n_deg + (self.deg % 360.0)
+            let n_rad = (self.rad / TAU).floor() * -TAU;
+            n_rad + (self.rad % TAU)
         }
         else
         {
-            self.deg
+            self.rad
         };
 
-        Self::degrees(angle)
+        Self::radians(angle)
+    }
+
+    /// Will wrap this Angle into (-180°,180°].
+    /// If this Angle would be 270°, then this method will return -90°
+    pub fn wrapped_symmetric(&self) -> Self
+    {
+        let angle = self.corrected();
+
+        if angle.rad > PI { angle - Self::FULL } else { angle }
     }
 
     /// Will return the correct angle in (-360°,360°).
     /// If this Angle would be -20°, then this method will return 340°
     pub fn range_corrected(&self) -> Self
     {
-        Self::degrees(self.deg % 360.0)
+        Self::radians(self.rad % TAU)
     }
 
     pub fn sin(&self) -> Scalar { self.rad.sin() }
@@ -208,4 +377,437 @@ impl Angle
     {
         (1.0 - alpha) * self + alpha * other
     }
+
+    /// Interpolate between `self` and `other` along the shorter way around the circle,
+    /// e.g. from 350° to 10° via 0°. The result isn't wrapped, so this example yields angles in [350°,370°].
+    pub fn lerp_shortest(&self, other: Angle, alpha: Scalar) -> Angle
+    {
+        let delta = (other - *self).wrapped_symmetric();
+
+        *self + alpha * delta
+    }
+
+    /// Angles from this Angle to `end` in steps of `step`, including both ends if `end` is hit exactly.
+    /// A positive step sweeps ccw, a negative one cw, wrapping around if needed,
+    /// e.g. from 350° to 10° by 5° gives 350°, 355°, ..., 370°. The angles aren't wrapped, use `.corrected()` for that.
+    /// If `end` is one or more full turns away from this Angle, exactly one full turn is swept.
+    /// A zero step only yields this Angle.
+    pub fn range_to(&self, end: Angle, step: Angle) -> impl Iterator<Item = Angle> + use<>
+    {
+        let difference = if step.rad < 0.0 { *self - end } else { end - *self };
+        let turns = difference.steps(Self::FULL);
+
+        let sweep = if turns != 0.0 && turns == turns.round() { Self::FULL } else { difference.corrected() };
+
+        // Snapped to whole steps within rounding errors, so the end is included
+        let count = if step.rad == 0.0 { 0 } else {
+            sweep.steps(step.abs()).floor() as usize
+        };
+
+        let start = *self;
+        (0..=count).map(move |n| start + step * n as Scalar)
+    }
+
+    /// Sum of the unit vectors pointing in the directions of `angles`, divided by their count.
+    fn mean_resultant(angles: &[Angle]) -> (Scalar, Scalar)
+    {
+        let (sin, cos) = angles.iter()
+            .map(|angle| angle.sin_cos())
+            .fold((0.0, 0.0), |(s, c), (sin, cos)| (s + sin, c + cos));
+
+        let n = angles.len() as Scalar;
+        (sin / n, cos / n)
+    }
+
+    /// Mean direction of `angles` in (-180°,180°], e.g. 350° and 10° average to 0° instead of 180°.
+    /// Returns `None` if there are no angles or they cancel out, e.g. 0° and 180°.
+    pub fn circular_mean(angles: &[Angle]) -> Option<Angle>
+    {
+        if angles.is_empty() {
+            return None
+        }
+
+        let (sin, cos) = Self::mean_resultant(angles);
+
+        if sin.hypot(cos) < 1e-6 { None } else { Some(Self::atan2(sin, cos)) }
+    }
+
+    /// Circular variance of `angles` in [0,1], i.e. 1 minus the length of their mean unit vector.
+    /// It's 0 if all angles point the same way and 1 if they cancel out. Returns 0 for no angles.
+    pub fn circular_variance(angles: &[Angle]) -> Scalar
+    {
+        if angles.is_empty() {
+            return 0.0
+        }
+
+        let (sin, cos) = Self::mean_resultant(angles);
+
+        (1.0 - sin.hypot(cos)).max(0.0)
+    }
+
+    /// This angle in radians wrapped into [0,2π), with -0 mapped to 0 and every NaN to the positive NaN.
+    fn canonical_rad(&self) -> Scalar
+    {
+        let rad = self.corrected().rad;
+
+        if rad.is_nan() { Scalar::NAN } else { rad + 0.0 }
+    }
+
+    /// Compare angles by their directions, i.e. after wrapping them into [0°,360°).
+    /// So -90° and 270° are equal and 350° is greater than 10°. NaNs, of either sign, are greater than all other angles.
+    pub fn total_cmp(&self, other: &Angle) -> core::cmp::Ordering
+    {
+        self.canonical_rad().total_cmp(&other.canonical_rad())
+    }
+}
+
+/// Error of parsing an angle, see `Angle::from_str()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseAngleError
+{
+    /// The string was empty or only whitespace.
+    Empty,
+    /// The number has no unit, e.g. `"90"`.
+    MissingUnit,
+    /// The unit isn't one of `deg`, `°`, `rad` or `turn`.
+    UnknownUnit(String),
+    /// The part before the unit isn't a number.
+    InvalidNumber(core::num::ParseFloatError),
+}
+
+impl core::fmt::Display for ParseAngleError
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self
+        {
+            ParseAngleError::Empty => write!(f, "cannot parse angle from empty string"),
+            ParseAngleError::MissingUnit => write!(f, "angle has no unit, expected one of deg, °, rad or turn"),
+            ParseAngleError::UnknownUnit(unit) => write!(f, "unknown angle unit \"{}\", expected one of deg, °, rad or turn", unit),
+            ParseAngleError::InvalidNumber(error) => write!(f, "invalid angle: {}", error),
+        }
+    }
+}
+
+impl core::error::Error for ParseAngleError
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self
+        {
+            ParseAngleError::InvalidNumber(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a number followed by a unit, e.g. `"90deg"`, `"90°"`, `"1.5708 rad"` or `"0.25turn"`.
+/// The unit is required, as a bare number could be either degrees or radians.
+impl core::str::FromStr for Angle
+{
+    type Err = ParseAngleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s.is_empty() {
+            return Err(ParseAngleError::Empty)
+        }
+
+        // Units and their size in radians
+        let units = [("deg", PI / 180.0), ("°", PI / 180.0), ("rad", 1.0), ("turn", TAU)];
+
+        for (suffix, unit) in units
+        {
+            // Don't mistake e.g. "grad" for "rad"
+            if let Some(number) = s.strip_suffix(suffix) && !number.ends_with(char::is_alphabetic) {
+                return number.trim_end().parse().map(|n: Scalar| Angle::radians(n * unit)).map_err(ParseAngleError::InvalidNumber)
+            }
+        }
+
+        if s.parse::<Scalar>().is_ok() {
+            return Err(ParseAngleError::MissingUnit)
+        }
+
+        // Everything after the longest prefix which is a number, which might be the unit.
+        // Without such a prefix, the whole input is reported.
+        let number_end = (1..=s.len()).rev()
+            .filter(|&end| s.is_char_boundary(end))
+            .find(|&end| s[..end].parse::<Scalar>().is_ok())
+            .unwrap_or(0);
+
+        Err(ParseAngleError::UnknownUnit(s[number_end..].trim_start().to_string()))
+    }
+}
+
+/// Angular velocity around a fixed axis. Multiply it by a `Duration` to get the angle turned during that time.
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, PartialOrd, Default, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign, derive_more::Neg
+)]
+pub struct AngularRate
+{
+    rad_per_sec: Scalar,
+}
+
+impl AngularRate
+{
+    pub const ZERO: AngularRate = AngularRate { rad_per_sec: 0.0 };
+
+    pub fn radians_per_second(rate: Scalar) -> Self
+    {
+        AngularRate { rad_per_sec: rate }
+    }
+
+    pub fn degrees_per_second(rate: Scalar) -> Self
+    {
+        AngularRate { rad_per_sec: rate.to_radians() }
+    }
+
+    /// The angle turned in one second.
+    pub fn angle(&self) -> Angle
+    {
+        Angle::radians(self.rad_per_sec)
+    }
+
+    pub fn rad_per_sec(&self) -> Scalar { self.rad_per_sec }
+
+    pub fn deg_per_sec(&self) -> Scalar { self.rad_per_sec.to_degrees() }
 }
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &AngularRate, rhs: &core::time::Duration| -> Angle {
+    Angle::radians(lhs.rad_per_sec * rhs.as_secs_f64() as Scalar)
+});
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &AngularRate, rhs: &Scalar| -> AngularRate {
+    AngularRate { rad_per_sec: lhs.rad_per_sec * rhs }
+});
+
+// Average rate needed to turn by an angle in the given time
+auto_ops::impl_op_ex!(/ |lhs: &Angle, rhs: &core::time::Duration| -> AngularRate {
+    AngularRate { rad_per_sec: lhs.rad / rhs.as_secs_f64() as Scalar }
+});
+
+/// Angle wrapped into [0°,360°), which is totally ordered (see `Angle::total_cmp()`) and hashable,
+/// e.g. to sort angles or use them as keys of a `BTreeMap`.
+#[derive(Debug, Clone, Copy)]
+pub struct CanonicalAngle(Angle);
+
+impl CanonicalAngle
+{
+    pub fn new(angle: Angle) -> Self
+    {
+        CanonicalAngle(Angle::radians(angle.canonical_rad()))
+    }
+
+    /// The wrapped angle in [0°,360°).
+    pub fn angle(&self) -> Angle { self.0 }
+}
+
+impl From<Angle> for CanonicalAngle
+{
+    fn from(angle: Angle) -> Self { CanonicalAngle::new(angle) }
+}
+
+impl From<CanonicalAngle> for Angle
+{
+    fn from(angle: CanonicalAngle) -> Self { angle.0 }
+}
+
+impl PartialEq for CanonicalAngle
+{
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == core::cmp::Ordering::Equal }
+}
+
+impl Eq for CanonicalAngle {}
+
+impl PartialOrd for CanonicalAngle
+{
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for CanonicalAngle
+{
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering { self.0.total_cmp(&other.0) }
+}
+
+impl core::hash::Hash for CanonicalAngle
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) { self.0.rad.to_bits().hash(state) }
+}
+
+/// Arc of the circle going ccw from `start` by `sweep`, e.g. a field of view or the yaw limits of a turret.
+/// Unlike comparing angles with min and max, this works across the 0°/360° seam.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct AngleInterval
+{
+    start: Angle,
+    sweep: Angle,
+}
+
+impl AngleInterval
+{
+    /// The whole circle.
+    pub const FULL: AngleInterval = AngleInterval { start: Angle::ZERO, sweep: Angle::FULL };
+
+    /// Interval from `start` going by `sweep`, cw if `sweep` is negative.
+    /// Sweeps larger than 360° are clamped to the whole circle.
+    pub fn new(start: Angle, sweep: Angle) -> Self
+    {
+        let start = if sweep.rad < 0.0 { start + sweep } else { start };
+
+        AngleInterval { start: start.corrected(), sweep: sweep.abs().min(Angle::FULL) }
+    }
+
+    /// Interval going ccw from `start` to `end`, e.g. from 350° to 10° covers 20°.
+    pub fn from_bounds(start: Angle, end: Angle) -> Self
+    {
+        Self::new(start, (end - start).corrected())
+    }
+
+    /// Start in [0°,360°).
+    pub fn start(&self) -> Angle { self.start }
+
+    /// Length in [0°,360°].
+    pub fn sweep(&self) -> Angle { self.sweep }
+
+    /// End in [0°,720°), i.e. not wrapped, so it's never smaller than the start.
+    pub fn end(&self) -> Angle { self.start + self.sweep }
+
+    pub fn is_full(&self) -> bool { self.sweep.rad >= TAU }
+
+    /// Distance of `angle` from the start, going ccw, in [0°,360°).
+    fn offset(&self, angle: Angle) -> Scalar
+    {
+        (angle - self.start).corrected().rad
+    }
+
+    /// Whether `angle` (or any angle differing from it by full turns) lies in this interval, including its bounds.
+    pub fn contains(&self, angle: Angle) -> bool
+    {
+        self.is_full() || self.offset(angle) <= self.sweep.rad
+    }
+
+    /// `angle` if it's inside this interval, otherwise the nearer bound.
+    pub fn clamp(&self, angle: Angle) -> Angle
+    {
+        if self.contains(angle) {
+            return angle
+        }
+
+        // Outside, so the offset is between the end and a full turn
+        let offset = self.offset(angle);
+
+        if offset - self.sweep.rad < TAU - offset { self.end().corrected() } else { self.start }
+    }
+
+    /// The parts covered by both intervals. These are up to two intervals,
+    /// e.g. two arcs of 300° overlap at both of their ends.
+    pub fn intersection(&self, other: &AngleInterval) -> Vec<AngleInterval>
+    {
+        if self.is_full() { return vec![*other] }
+        if other.is_full() { return vec![*self] }
+
+        // Measured from self's start, self covers [0, s] and other covers [o, o + t] as well as one turn earlier
+        let (s, t) = (self.sweep.rad, other.sweep.rad);
+        let o = self.offset(other.start);
+
+        [o, o - TAU].into_iter()
+            .filter_map(|lo| {
+                let (from, to) = (lo.max(0.0), (lo + t).min(s));
+                (from <= to).then(|| Self::new(self.start + Angle::radians(from), Angle::radians(to - from)))
+            })
+            .collect()
+    }
+
+    /// The smallest interval covering exactly both intervals.
+    /// Returns `None` if they don't touch, as their union would consist of two parts.
+    pub fn union(&self, other: &AngleInterval) -> Option<AngleInterval>
+    {
+        if self.is_full() || other.is_full() {
+            return Some(Self::FULL)
+        }
+
+        let (s, t) = (self.sweep.rad, other.sweep.rad);
+        let o = self.offset(other.start);
+
+        // Either other starts inside self, or it reaches over the end of the turn into self's start
+        let (start, end) = if o <= s {
+            (0.0, s.max(o + t))
+        }
+        else if o + t >= TAU {
+            (o, (o + t).max(s + TAU))
+        }
+        else {
+            return None
+        };
+
+        if end - start >= TAU {
+            return Some(Self::FULL)
+        }
+
+        Some(Self::new(self.start + Angle::radians(start), Angle::radians(end - start)))
+    }
+}
+
+/// Angle which always stays in [0°,360°), also after adding, subtracting, negating or scaling it.
+/// Use `CanonicalAngle` instead to sort or hash angles.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct WrappedAngle(Angle);
+
+impl WrappedAngle
+{
+    pub const ZERO: WrappedAngle = WrappedAngle(Angle::ZERO);
+
+    pub fn new(angle: Angle) -> Self
+    {
+        WrappedAngle(Angle::radians(angle.canonical_rad()))
+    }
+
+    /// The angle in [0°,360°).
+    pub fn angle(&self) -> Angle { self.0 }
+
+    pub fn rad(&self) -> Scalar { self.0.rad }
+
+    pub fn deg(&self) -> Scalar { self.0.deg() }
+}
+
+impl core::fmt::Display for WrappedAngle
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<Angle> for WrappedAngle
+{
+    fn from(angle: Angle) -> Self { WrappedAngle::new(angle) }
+}
+
+impl From<WrappedAngle> for Angle
+{
+    fn from(angle: WrappedAngle) -> Self { angle.0 }
+}
+
+impl From<WrappedAngle> for CanonicalAngle
+{
+    fn from(angle: WrappedAngle) -> Self { CanonicalAngle(angle.0) }
+}
+
+auto_ops::impl_op_ex!(+ |lhs: &WrappedAngle, rhs: &WrappedAngle| -> WrappedAngle { WrappedAngle::new(lhs.0 + rhs.0) });
+auto_ops::impl_op_ex_commutative!(+ |lhs: &WrappedAngle, rhs: &Angle| -> WrappedAngle { WrappedAngle::new(lhs.0 + *rhs) });
+auto_ops::impl_op_ex!(+= |lhs: &mut WrappedAngle, rhs: &WrappedAngle| { *lhs = *lhs + rhs; });
+auto_ops::impl_op_ex!(+= |lhs: &mut WrappedAngle, rhs: &Angle| { *lhs = *lhs + rhs; });
+
+auto_ops::impl_op_ex!(- |lhs: &WrappedAngle, rhs: &WrappedAngle| -> WrappedAngle { WrappedAngle::new(lhs.0 - rhs.0) });
+auto_ops::impl_op_ex!(- |lhs: &WrappedAngle, rhs: &Angle| -> WrappedAngle { WrappedAngle::new(lhs.0 - *rhs) });
+auto_ops::impl_op_ex!(-= |lhs: &mut WrappedAngle, rhs: &WrappedAngle| { *lhs = *lhs - rhs; });
+auto_ops::impl_op_ex!(-= |lhs: &mut WrappedAngle, rhs: &Angle| { *lhs = *lhs - rhs; });
+
+auto_ops::impl_op_ex!(- |a: &WrappedAngle| -> WrappedAngle { WrappedAngle::new(-a.0) });
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &WrappedAngle, rhs: &Scalar| -> WrappedAngle { WrappedAngle::new(lhs.0 * rhs) });
+auto_ops::impl_op_ex!(*= |lhs: &mut WrappedAngle, rhs: &Scalar| { *lhs = *lhs * rhs; });
+auto_ops::impl_op_ex!(/ |lhs: &WrappedAngle, rhs: &Scalar| -> WrappedAngle { WrappedAngle::new(lhs.0 / rhs) });
+auto_ops::impl_op_ex!(/= |lhs: &mut WrappedAngle, rhs: &Scalar| { *lhs = *lhs / rhs; });
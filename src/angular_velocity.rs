@@ -0,0 +1,53 @@
+//! Angular velocities with clear units, so radians, degrees and radians per second don't get mixed up.
+
+pub use crate::axis_angle::{AxisAngle, Quaternion, Angle};
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+use core::time::Duration;
+
+/// Angular velocity in radians per second. The direction is the rotation axis.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct AngularVelocity(pub [Scalar; 3]);
+
+impl AngularVelocity
+{
+    pub const ZERO: AngularVelocity = AngularVelocity([0.0; 3]);
+
+    /// Rotate around `axis` by `angle` per second
+    pub fn from_axis(axis: &[Scalar], angle_per_second: Angle) -> Self
+    {
+        let n = angle_per_second.rad() / (axis[0]*axis[0] + axis[1]*axis[1] + axis[2]*axis[2]).sqrt();
+
+        AngularVelocity([n * axis[0], n * axis[1], n * axis[2]])
+    }
+
+    /// Angle rotated per second
+    pub fn speed(&self) -> Angle
+    {
+        let [x, y, z] = self.0;
+
+        Angle::radians((x*x + y*y + z*z).sqrt())
+    }
+
+    /// The rotation after `seconds` seconds
+    pub fn rotation_after(&self, seconds: Scalar) -> AxisAngle
+    {
+        let speed = self.speed();
+
+        // Only exactly zero, since even tiny rates (e.g. of a gyroscope) add up over time
+        if speed.rad() == 0.0 {
+            return AxisAngle::IDENTITY
+        }
+
+        AxisAngle::new(&self.0, speed * seconds)
+    }
+}
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &AngularVelocity, rhs: &Duration| -> AxisAngle { lhs.rotation_after(rhs.as_secs_f64() as Scalar) });
+auto_ops::impl_op_ex_commutative!(* |lhs: &AngularVelocity, rhs: &Scalar| -> AngularVelocity { AngularVelocity(lhs.0.map(|x| x * rhs)) });
+auto_ops::impl_op_ex!(+ |lhs: &AngularVelocity, rhs: &AngularVelocity| -> AngularVelocity {
+    AngularVelocity(core::array::from_fn(|i| lhs.0[i] + rhs.0[i]))
+});
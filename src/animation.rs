@@ -0,0 +1,142 @@
+//! Animation tracks, i.e. poses at given points in time, sampled in between.
+
+pub use crate::spline::{DualQuaternion, MotorCatmullRom};
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+use alloc::vec::Vec;
+
+/// How poses between two keyframes are computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interpolation
+{
+    /// Keep the pose of the previous keyframe.
+    Step,
+    /// Screw-lerp between the surrounding keyframes.
+    Sclerp,
+    /// Catmull–Rom spline through all keyframes. The tangents are scaled by the time between keyframes,
+    /// so the velocity doesn't jump at keyframes, even if they aren't evenly spaced.
+    Spline,
+}
+
+/// What happens when a track is sampled outside of its time range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoopMode
+{
+    /// Hold the first or last pose.
+    Clamp,
+    /// Start over from the beginning.
+    Repeat,
+    /// Play alternately forwards and backwards.
+    PingPong,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe
+{
+    pub time: Scalar,
+    pub pose: DualQuaternion,
+}
+
+/// Keyframes sorted by time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseTrack
+{
+    keys: Vec<Keyframe>,
+    spline: MotorCatmullRom,
+    pub interpolation: Interpolation,
+    pub loop_mode: LoopMode,
+}
+
+impl Default for PoseTrack
+{
+    fn default() -> Self { PoseTrack::new(Interpolation::Sclerp, LoopMode::Clamp) }
+}
+
+impl PoseTrack
+{
+    /// An empty track.
+    pub fn new(interpolation: Interpolation, loop_mode: LoopMode) -> Self
+    {
+        PoseTrack { keys: Vec::new(), spline: MotorCatmullRom::new(&[], 0.0), interpolation, loop_mode }
+    }
+
+    /// Add a keyframe with a normalized pose. A keyframe at the same time gets replaced.
+    pub fn insert(&mut self, time: Scalar, pose: DualQuaternion)
+    {
+        let index = self.keys.partition_point(|key| key.time < time);
+
+        match self.keys.get_mut(index)
+        {
+            Some(key) if key.time == time => key.pose = pose,
+            _ => self.keys.insert(index, Keyframe { time, pose }),
+        }
+
+        // Also flips the poses into the same hemispheres as their predecessors
+        self.spline = MotorCatmullRom::new(&self.keys.iter().map(|key| key.pose).collect::<Vec<_>>(), 0.0);
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] { &self.keys }
+
+    pub fn is_empty(&self) -> bool { self.keys.is_empty() }
+
+    /// Time of the first keyframe, 0.0 for empty tracks.
+    pub fn start(&self) -> Scalar { self.keys.first().map_or(0.0, |key| key.time) }
+
+    /// Time of the last keyframe, 0.0 for empty tracks.
+    pub fn end(&self) -> Scalar { self.keys.last().map_or(0.0, |key| key.time) }
+
+    pub fn duration(&self) -> Scalar { self.end() - self.start() }
+
+    /// Map `time` into the track's time range according to the loop mode.
+    fn local_time(&self, time: Scalar) -> Scalar
+    {
+        let duration = self.duration();
+
+        if duration <= 0.0 {
+            return self.start()
+        }
+
+        let t = time - self.start();
+
+        let t = match self.loop_mode
+        {
+            LoopMode::Clamp    => t.clamp(0.0, duration),
+            LoopMode::Repeat   => t.rem_euclid(duration),
+            LoopMode::PingPong => {
+                let t = t.rem_euclid(2.0 * duration);
+                if t > duration { 2.0 * duration - t } else { t }
+            }
+        };
+
+        self.start() + t
+    }
+
+    /// The pose at `time`. Returns `None` if there are no keyframes.
+    pub fn sample(&self, time: Scalar) -> Option<DualQuaternion>
+    {
+        if self.keys.len() < 2 {
+            return self.keys.first().map(|key| key.pose)
+        }
+
+        let time = self.local_time(time);
+
+        // Segment between key i and i+1 containing time
+        let i = self.keys.partition_point(|key| key.time <= time).clamp(1, self.keys.len() - 1) - 1;
+        let (a, b) = (&self.keys[i], &self.keys[i + 1]);
+        let alpha = ((time - a.time) / (b.time - a.time)).clamp(0.0, 1.0);
+
+        // The spline's waypoints are the hemisphere-aligned poses
+        let poses = self.spline.waypoints();
+
+        Some(match self.interpolation
+        {
+            Interpolation::Step   => if alpha >= 1.0 { poses[i + 1] } else { poses[i] },
+            Interpolation::Sclerp => poses[i].sclerp(&poses[i + 1], alpha),
+            Interpolation::Spline => {
+                self.spline.evaluate_segment_with_durations(i, alpha, |j| self.keys[j + 1].time - self.keys[j].time)
+            }
+        })
+    }
+}
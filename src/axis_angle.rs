@@ -0,0 +1,86 @@
+//! Rotations given by a rotation axis and an angle, which is the most human-readable form of a rotation.
+
+pub use crate::dual_quaternion::{DualQuaternion, Quaternion, Angle};
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+/// Rotation by `angle` (counter-clockwise) around `axis`. The axis is normalized.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct AxisAngle
+{
+    pub axis:  [Scalar; 3],
+    pub angle: Angle,
+}
+
+impl From<AxisAngle> for Quaternion
+{
+    fn from(aa: AxisAngle) -> Self { Quaternion::rotor(aa.angle, &aa.axis) }
+}
+
+impl From<AxisAngle> for DualQuaternion
+{
+    fn from(aa: AxisAngle) -> Self { DualQuaternion::rotor(aa.angle, &aa.axis) }
+}
+
+impl From<Quaternion> for AxisAngle
+{
+    fn from(q: Quaternion) -> Self { AxisAngle::from_quaternion(&q) }
+}
+
+/// Ignores the translation of the motor.
+impl From<DualQuaternion> for AxisAngle
+{
+    fn from(dq: DualQuaternion) -> Self { AxisAngle::from_quaternion(&dq.rotation()) }
+}
+
+impl AxisAngle
+{
+    /// No rotation
+    pub const IDENTITY: AxisAngle = AxisAngle { axis: [1.0, 0.0, 0.0], angle: Angle::ZERO };
+
+    /// Create a new rotation. The axis will be normalized.
+    pub fn new(axis: &[Scalar], angle: Angle) -> Self
+    {
+        let n = 1.0 / (axis[0]*axis[0] + axis[1]*axis[1] + axis[2]*axis[2]).sqrt();
+
+        AxisAngle { axis: [n * axis[0], n * axis[1], n * axis[2]], angle }
+    }
+
+    /// Extract axis and angle from a rotor. The angle will be in [0°,360°).
+    pub fn from_quaternion(q: &Quaternion) -> Self
+    {
+        let q = q.normalized();
+        let sin = (q.i*q.i + q.j*q.j + q.k*q.k).sqrt();
+
+        // No rotation, so the axis doesn't matter.
+        // Only exactly zero, small rotations still have a well defined axis.
+        if sin == 0.0 {
+            return Self::IDENTITY
+        }
+
+        AxisAngle {
+            axis:  [q.i / sin, q.j / sin, q.k / sin],
+            angle: Angle::radians(2.0 * sin.atan2(q.w)),
+        }
+    }
+
+    /// Rotate by the opposite angle.
+    pub fn inverse(&self) -> Self
+    {
+        AxisAngle { axis: self.axis, angle: -self.angle }
+    }
+}
+
+// Scales the angle
+auto_ops::impl_op_ex_commutative!(* |lhs: &AxisAngle, rhs: &Scalar| -> AxisAngle { AxisAngle { axis: lhs.axis, angle: lhs.angle * rhs } });
+
+// First rotate by `rhs`, then by `lhs` (same as for Quaternions)
+auto_ops::impl_op_ex!(* |lhs: &AxisAngle, rhs: &AxisAngle| -> AxisAngle {
+    AxisAngle::from_quaternion(&(Quaternion::from(*lhs) * Quaternion::from(*rhs)))
+});
+
+// Rotate a Quaternion (e.g. an orientation) further, i.e. q -> aa * q
+auto_ops::impl_op_ex!(+= |lhs: &mut Quaternion, rhs: &AxisAngle| { *lhs = Quaternion::from(*rhs) * *lhs });
@@ -0,0 +1,96 @@
+//! Bivectors are oriented plane segments, e.g. the wedge product of two vectors.
+//! The imaginary units i, j, k of quaternions are just the bivectors of the yz-, zx- and xy-plane,
+//! so rotors are exponentials of bivectors.
+
+pub use crate::quaternion::{Quaternion, Angle};
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
+    derive_more::Neg, derive_more::From
+)]
+pub struct Bivector3
+{
+    pub yz: Scalar,
+    pub zx: Scalar,
+    pub xy: Scalar,
+}
+
+/// Pure quaternion with i = yz, j = zx, k = xy
+impl From<Bivector3> for Quaternion
+{
+    fn from(b: Bivector3) -> Self { Quaternion { w: 0.0, i: b.yz, j: b.zx, k: b.xy } }
+}
+
+impl Bivector3
+{
+    pub const ZERO: Self = Self { yz: 0.0, zx: 0.0, xy: 0.0 };
+    pub const YZ:   Self = Self { yz: 1.0, ..Self::ZERO };
+    pub const ZX:   Self = Self { zx: 1.0, ..Self::ZERO };
+    pub const XY:   Self = Self { xy: 1.0, ..Self::ZERO };
+
+    pub fn new(yz: Scalar, zx: Scalar, xy: Scalar) -> Self
+    {
+        Bivector3 { yz, zx, xy }
+    }
+
+    /// Wedge (outer) product of two 3D-vectors, i.e. the oriented parallelogram spanned by `a` and `b`.
+    pub fn wedge(a: &[Scalar], b: &[Scalar]) -> Self
+    {
+        // Same components as the cross product, which is the dual of the wedge product
+        Bivector3 {
+            yz: a[1] * b[2] - a[2] * b[1],
+            zx: a[2] * b[0] - a[0] * b[2],
+            xy: a[0] * b[1] - a[1] * b[0],
+        }
+    }
+
+    /// The imaginary part of a quaternion
+    pub fn from_quaternion(q: &Quaternion) -> Self
+    {
+        Bivector3 { yz: q.i, zx: q.j, xy: q.k }
+    }
+
+    /// Area of the plane segment
+    pub fn norm(&self) -> Scalar
+    {
+        (self.yz*self.yz + self.zx*self.zx + self.xy*self.xy).sqrt()
+    }
+
+    pub fn normalized(&self) -> Self
+    {
+        *self * (1.0 / self.norm())
+    }
+
+    /// The normal of the plane, i.e. the vector whose cross product has the same components.
+    pub fn dual(&self) -> [Scalar; 3]
+    {
+        [self.yz, self.zx, self.xy]
+    }
+
+    /// Exponential of this bivector, which is a rotor rotating by twice the norm in the plane of this bivector.
+    pub fn exp(&self) -> Quaternion
+    {
+        Quaternion::from(*self).exp()
+    }
+
+    /// Rotor rotating by `angle` in the plane of this bivector.
+    /// The rotation goes from `a` towards `b` if this bivector is `Bivector3::wedge(a,b)`.
+    pub fn rotor(&self, angle: Angle) -> Quaternion
+    {
+        (self.normalized() * (0.5 * angle.rad())).exp()
+    }
+}
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &Bivector3, rhs: &Scalar| -> Bivector3 {
+    Bivector3 {
+        yz: lhs.yz * rhs,
+        zx: lhs.zx * rhs,
+        xy: lhs.xy * rhs
+    }
+});
@@ -1,9 +1,13 @@
-use crate::util::Scalar;
+use crate::util::{Scalar, write_terms};
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+use crate::vector2::Vector2;
+use alloc::string::String;
 pub use crate::angle::Angle;
 
 #[repr(C)]
 #[derive(
-    Debug, Clone, Copy, PartialEq, PartialOrd, bytemuck::Pod, bytemuck::Zeroable,
+    Debug, Clone, Copy, PartialEq, PartialOrd, Default, bytemuck::Pod, bytemuck::Zeroable,
     derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
     derive_more::Neg, derive_more::From
 )]
@@ -23,23 +27,106 @@ impl From<&Scalar> for Complex
     fn from(value: &Scalar) -> Self { Complex { re: *value, im: 0.0 } }
 }
 
-impl std::fmt::Display for Complex
+/// `num_complex::Complex32`, or `Complex64` with the `use_f64` feature
+#[cfg(feature = "num-complex")]
+impl From<num_complex::Complex<Scalar>> for Complex
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.re.powi(2) > Scalar::EPSILON {
-            write!(f, "{}",  self.re);
-
-            if self.im.powi(2) > Scalar::EPSILON
-            {
-                write!(f, " + ");
-            }
+    fn from(value: num_complex::Complex<Scalar>) -> Self { Complex { re: value.re, im: value.im } }
+}
+
+#[cfg(feature = "num-complex")]
+impl From<Complex> for num_complex::Complex<Scalar>
+{
+    fn from(value: Complex) -> Self { num_complex::Complex::new(value.re, value.im) }
+}
+
+impl core::fmt::Display for Complex
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_terms(f, &[(self.re, ""), (self.im, "i")])
+    }
+}
+
+/// Error of parsing a complex number, see `Complex::from_str()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseComplexError
+{
+    /// The string was empty or only whitespace.
+    Empty,
+    /// The real part isn't a number.
+    InvalidReal(core::num::ParseFloatError),
+    /// The imaginary part (without the `i`) isn't a number.
+    InvalidImaginary(core::num::ParseFloatError),
+}
+
+impl core::fmt::Display for ParseComplexError
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self
+        {
+            ParseComplexError::Empty => write!(f, "cannot parse complex number from empty string"),
+            ParseComplexError::InvalidReal(error) => write!(f, "invalid real part: {}", error),
+            ParseComplexError::InvalidImaginary(error) => write!(f, "invalid imaginary part: {}", error),
+        }
+    }
+}
+
+impl core::error::Error for ParseComplexError
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self
+        {
+            ParseComplexError::InvalidReal(error) | ParseComplexError::InvalidImaginary(error) => Some(error),
+            ParseComplexError::Empty => None,
         }
+    }
+}
+
+/// Parses the format written by `Display`, e.g. `"3 + 4i"`, `"3 - 4i"`, `"-2i"`, `"i"` or `"5"`.
+/// Whitespace is ignored.
+impl core::str::FromStr for Complex
+{
+    type Err = ParseComplexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
 
-        if self.im.powi(2) > Scalar::EPSILON {
-            write!(f, "{}i", self.im);
+        if s.is_empty() {
+            return Err(ParseComplexError::Empty)
         }
 
-        write!(f, "")
+        let real = |s: &str| s.parse::<Scalar>().map_err(ParseComplexError::InvalidReal);
+
+        let Some(s) = s.strip_suffix('i') else {
+            return Ok(Complex { re: real(&s)?, im: 0.0 })
+        };
+
+        // The sign between both parts, skipping a leading sign and the signs of exponents like 1e-3
+        let bytes = s.as_bytes();
+        let split = (1..s.len()).rev()
+            .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E' | b'+' | b'-'));
+
+        let (re, im) = match split
+        {
+            Some(i) => (real(&s[..i])?, &s[i..]),
+            None    => (0.0, s),
+        };
+
+        // The imaginary part may have a sign of its own (like "3 + -4i") or no digits (like "-i")
+        let (sign, digits) = match im.as_bytes().first()
+        {
+            Some(b'-') => (-1.0, &im[1..]),
+            Some(b'+') => (1.0, &im[1..]),
+            _          => (1.0, im),
+        };
+
+        let im = match digits
+        {
+            "" => 1.0,
+            _  => digits.parse::<Scalar>().map_err(ParseComplexError::InvalidImaginary)?,
+        };
+
+        Ok(Complex { re, im: sign * im })
     }
 }
 
@@ -48,6 +135,8 @@ impl Complex
     pub const ZERO: Complex = Complex { re: 0.0, im: 0.0 };
     pub const ONE:  Complex = Complex { re: 1.0, im: 0.0 };
     pub const IMAG: Complex = Complex { re: 0.0, im: 1.0 };
+    /// The imaginary unit, same as `IMAG`
+    pub const I:    Complex = Self::IMAG;
 
     pub fn new(re: Scalar, im: Scalar) -> Self { Self { re, im } }
 
@@ -61,6 +150,15 @@ impl Complex
         }
     }
 
+    /// Unit complex number `cos + i sin` of `angle`, which rotates counter-clockwise by `angle` when multiplied.
+    /// The 2D analogue of `Quaternion::rotor()`.
+    pub fn from_angle(angle: Angle) -> Self
+    {
+        let (sin, cos) = angle.sin_cos();
+
+        Self { re: cos, im: sin }
+    }
+
     /// Get this complex number's angle (counter-clockwise).
     pub fn angle(&self) -> Angle {
         Angle::radians( self.im.atan2(self.re) )
@@ -69,13 +167,25 @@ impl Complex
     /// Conjugate, i.e. negate the imaginary part
     pub fn conj(&self) -> Self { Self { re: self.re, im: -self.im } }
 
+    /// Squared norm of this complex number, cheaper than `norm()`
+    pub fn norm_sqr(&self) -> Scalar { self.re*self.re + self.im*self.im }
+
     /// Norm of this complex number
-    pub fn norm(&self) -> Scalar { (self.re*self.re + self.im*self.im).sqrt() }
+    pub fn norm(&self) -> Scalar { self.norm_sqr().sqrt() }
+
+    /// Multiplicative inverse, i.e. `1/self`. Returns `None` for zero.
+    pub fn inverse(&self) -> Option<Self>
+    {
+        let n = self.norm_sqr();
+
+        (n != 0.0).then(|| self.conj() / n)
+    }
 
     /// Normalize this complex number
     pub fn normalized(&self) -> Self { *self * (1.0 / self.norm()) }
 
-    /// May produce invalid numbers if this complex number is 0.0
+    /// Principal square root, i.e. the one with non-negative real part.
+    /// The branch cut is the negative real axis, where the sign of the (zero) imaginary part decides the result.
     pub fn sqrt(&self) -> Self
     {
         //     a+bi = r*exp(ix)
@@ -85,9 +195,9 @@ impl Complex
         //  x = atan2(b,a)
 
         let x = self.im.atan2(self.re);
-        let (sin,cos) = x.sin_cos();
+        let (sin,cos) = (0.5*x).sin_cos();
 
-        let r = self.norm();
+        let r = self.norm().sqrt();
 
         Self {
             re: r*cos,
@@ -109,8 +219,8 @@ impl Complex
         }
     }
 
-    /// Natural (principal value) logarithm for complex numbers.
-    /// This may return invalid numbers if .re <= 0.0
+    /// Natural (principal value) logarithm for complex numbers, its imaginary part is in (-π,π].
+    /// The branch cut is the negative real axis. The logarithm of 0.0 has a real part of -∞.
     pub fn log(&self) -> Self
     {
         // https://en.wikipedia.org/wiki/Complex_logarithm
@@ -156,8 +266,8 @@ impl Complex
     }
 
 
-    /// Raise a complex number to some (real) power.
-    /// This may return invalid numbers if this complex number is 0.0
+    /// Raise a complex number to some (real) power, using the principal logarithm (see `Complex::log()`).
+    /// 0.0 raised to a negative power is infinite.
     pub fn powf(&self, f: Scalar) -> Self
     {
         // (a+bi)^n = r^n * exp(i*nx) = r^n * (cos(nx) + i*sin(nx))
@@ -176,7 +286,7 @@ impl Complex
     }
 
     /// Raise a complex number to some integer power.
-    /// This may return invalid numbers if this complex number is 0.0
+    /// 0.0 raised to a negative power is infinite.
     pub fn powi(&self, i: i32) -> Self
     {
         // (a+bi)^n = r^n * exp(i*nx) = r^n * (cos(nx) + i*sin(nx))
@@ -194,13 +304,64 @@ impl Complex
         }
     }
 
-    /// Raise a complex number to some complex power.
-    /// This may return invalid numbers if this complex number's real part <= 0.0
+    /// Raise a complex number to some complex power, using the principal logarithm (see `Complex::log()`).
+    /// This may return invalid numbers if this complex number is 0.0
     pub fn pow(&self, z: Complex) -> Self
     {
         ( z * self.log() ).exp()
     }
 
+    /// All `n` complex numbers whose `n`-th power is this number, starting with the principal root
+    /// and going counter-clockwise. Yields nothing for `n == 0`.
+    pub fn nth_roots(&self, n: u32) -> impl Iterator<Item = Complex> + use<>
+    {
+        // The roots are evenly spread on a circle of radius r^(1/n), starting at the angle x/n
+        let r = self.norm().powf(1.0 / n as Scalar);
+        let angle = self.angle() / n as Scalar;
+
+        (0..n).map(move |k| Complex::polar(r, angle + Angle::FULL * (k as Scalar / n as Scalar)))
+    }
+
+    /// The `n` complex numbers whose `n`-th power is 1, i.e. `n` evenly spread points on the unit circle starting at 1.
+    pub fn roots_of_unity(n: u32) -> impl Iterator<Item = Complex>
+    {
+        Complex::ONE.nth_roots(n)
+    }
+
+    /// Rotate the point `p` around the origin by multiplying it with this complex number.
+    /// A unit complex number only rotates, otherwise `p` also gets scaled by the norm. See also `Rotor2D`.
+    pub fn rotate(&self, p: &Vector2) -> Vector2
+    {
+        let p = self * Complex { re: p.x, im: p.y };
+
+        Vector2 { x: p.re, y: p.im }
+    }
+
+    /// Rotate many points in place, see `Complex::rotate()`.
+    pub fn rotate_points_in_place(&self, points: &mut [Vector2])
+    {
+        // Simple loop without branches, so the compiler can vectorize it
+        let Complex { re, im } = *self;
+
+        for p in points.iter_mut()
+        {
+            *p = Vector2 { x: re * p.x - im * p.y, y: im * p.x + re * p.y };
+        }
+    }
+
+    /// Multiply `a` and `b` element-wise into `out`, e.g. to apply phasors to a signal.
+    /// Panics if the slices have different lengths.
+    pub fn mul_slices(a: &[Complex], b: &[Complex], out: &mut [Complex])
+    {
+        assert!(a.len() == b.len() && a.len() == out.len(), "slices must have the same length");
+
+        // Simple loop without branches, so the compiler can vectorize it
+        for ((out, a), b) in out.iter_mut().zip(a).zip(b)
+        {
+            *out = a * b;
+        }
+    }
+
     /// Linearily interpolate between this and `other`
     pub fn lerp(&self, other: Complex, alpha: Scalar) -> Complex
     {
@@ -229,8 +390,7 @@ auto_ops::impl_op_ex!(* |lhs: &Complex, rhs: &Complex| -> Complex {
     }
 });
 auto_ops::impl_op_ex!(*= |lhs: &mut Complex, rhs: &Complex| {
-    lhs.re = lhs.re * rhs.re - lhs.im * rhs.im;
-    lhs.im = lhs.im * rhs.re + lhs.re * rhs.im;
+    *lhs = *lhs * rhs;
 });
 auto_ops::impl_op_ex_commutative!(* |lhs: &Complex, rhs: &Scalar| -> Complex {
     Complex
@@ -244,8 +404,8 @@ auto_ops::impl_op_ex!(*= |lhs: &mut Complex, rhs: &Scalar| {
     lhs.im *= rhs;
 });
 
-auto_ops::impl_op_ex!(/ |lhs: &Complex, rhs: &Complex| -> Complex { lhs * rhs.conj() * (1.0 / rhs.norm().powi(2) ) });
-auto_ops::impl_op_ex!(/= |lhs: &mut Complex, rhs: &Complex| { *lhs *= rhs.conj() * (1.0 / rhs.norm().powi(2) ) });
+auto_ops::impl_op_ex!(/ |lhs: &Complex, rhs: &Complex| -> Complex { lhs * rhs.conj() * (1.0 / rhs.norm_sqr()) });
+auto_ops::impl_op_ex!(/= |lhs: &mut Complex, rhs: &Complex| { *lhs *= rhs.conj() * (1.0 / rhs.norm_sqr()) });
 auto_ops::impl_op_ex!(/ |lhs: &Complex, rhs: &Scalar| -> Complex {
     Complex
     {
@@ -253,7 +413,7 @@ auto_ops::impl_op_ex!(/ |lhs: &Complex, rhs: &Scalar| -> Complex {
         im: lhs.im / rhs
     }
 });
-auto_ops::impl_op_ex!(/ |lhs: &Scalar, rhs: &Complex| -> Complex { lhs * rhs.conj() * (1.0 / rhs.norm().powi(2) ) });
+auto_ops::impl_op_ex!(/ |lhs: &Scalar, rhs: &Complex| -> Complex { lhs * rhs.conj() * (1.0 / rhs.norm_sqr()) });
 auto_ops::impl_op_ex!(/= |lhs: &mut Complex, rhs: &Scalar| {
     lhs.re /= rhs;
     lhs.im /= rhs;
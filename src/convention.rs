@@ -0,0 +1,148 @@
+//! Coordinate conventions, i.e. which axis points right, up and forward, and conversions between them.
+//! Different tools (Blender, Unity, ROS, ...) use different conventions, which is a common source of rotation bugs.
+
+pub use crate::dual_quaternion::{DualQuaternion, Quaternion};
+
+use crate::util::Scalar;
+
+/// A signed coordinate axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis
+{
+    PosX, NegX,
+    PosY, NegY,
+    PosZ, NegZ,
+}
+
+impl Axis
+{
+    /// Unit vector of this axis.
+    pub fn vector(&self) -> [Scalar; 3]
+    {
+        match self
+        {
+            Axis::PosX => [ 1.0,  0.0,  0.0],
+            Axis::NegX => [-1.0,  0.0,  0.0],
+            Axis::PosY => [ 0.0,  1.0,  0.0],
+            Axis::NegY => [ 0.0, -1.0,  0.0],
+            Axis::PosZ => [ 0.0,  0.0,  1.0],
+            Axis::NegZ => [ 0.0,  0.0, -1.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Handedness
+{
+    Left,
+    Right,
+}
+
+/// Describes which axis points right, up and forward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Convention
+{
+    right:   Axis,
+    up:      Axis,
+    forward: Axis,
+}
+
+impl Convention
+{
+    /// Y-up, right-handed, forward is -Z (OpenGL, glTF)
+    pub const OPENGL:  Convention = Convention { right: Axis::PosX, up: Axis::PosY, forward: Axis::NegZ };
+
+    /// Y-up, left-handed, forward is +Z
+    pub const UNITY:   Convention = Convention { right: Axis::PosX, up: Axis::PosY, forward: Axis::PosZ };
+
+    /// Z-up, right-handed, forward is +Y
+    pub const BLENDER: Convention = Convention { right: Axis::PosX, up: Axis::PosZ, forward: Axis::PosY };
+
+    /// Z-up, right-handed, forward is +X and left is +Y
+    pub const ROS:     Convention = Convention { right: Axis::NegY, up: Axis::PosZ, forward: Axis::PosX };
+
+    /// Create a new convention.
+    /// Returns `None` if two of the axes lie on the same line.
+    pub fn new(right: Axis, up: Axis, forward: Axis) -> Option<Self>
+    {
+        let (r, u, f) = (right.vector(), up.vector(), forward.vector());
+
+        let dot = |a: [Scalar; 3], b: [Scalar; 3]| a[0]*b[0] + a[1]*b[1] + a[2]*b[2];
+
+        if dot(r, u) != 0.0 || dot(r, f) != 0.0 || dot(u, f) != 0.0 {
+            return None
+        }
+
+        Some(Convention { right, up, forward })
+    }
+
+    pub fn right(&self)   -> Axis { self.right }
+    pub fn up(&self)      -> Axis { self.up }
+    pub fn forward(&self) -> Axis { self.forward }
+
+    /// Right-handed if right X up = backward (= -forward).
+    pub fn handedness(&self) -> Handedness
+    {
+        let (r, u, f) = (self.right.vector(), self.up.vector(), self.forward.vector());
+
+        let cross = [
+            r[1] * u[2] - r[2] * u[1],
+            r[2] * u[0] - r[0] * u[2],
+            r[0] * u[1] - r[1] * u[0],
+        ];
+
+        if cross[0]*f[0] + cross[1]*f[1] + cross[2]*f[2] < 0.0 { Handedness::Right } else { Handedness::Left }
+    }
+
+    /// Column-major matrix taking coordinates in this convention to coordinates in `to`.
+    /// It's a signed permutation matrix.
+    fn matrix_to(&self, to: &Convention) -> [[Scalar; 3]; 3]
+    {
+        // First get the components along right/up/forward, then build the vector in the other convention.
+        // M = B * A^T, where A and B have right/up/forward as columns.
+
+        let a = [self.right.vector(), self.up.vector(), self.forward.vector()];
+        let b = [to.right.vector(),   to.up.vector(),   to.forward.vector()];
+
+        core::array::from_fn(|col| core::array::from_fn(|row| {
+            (0..3).map(|k| b[k][row] * a[k][col]).sum()
+        }))
+    }
+
+    /// Convert a point from this convention to `to`.
+    pub fn convert_point(&self, to: &Convention, point: &[Scalar]) -> [Scalar; 3]
+    {
+        let m = self.matrix_to(to);
+
+        core::array::from_fn(|row| m[0][row] * point[0] + m[1][row] * point[1] + m[2][row] * point[2])
+    }
+
+    /// Convert a direction from this convention to `to`.
+    pub fn convert_direction(&self, to: &Convention, direction: &[Scalar]) -> [Scalar; 3]
+    {
+        self.convert_point(to, direction)
+    }
+
+    /// Convert a rotation from this convention to `to`.
+    pub fn convert_quaternion(&self, to: &Convention, q: &Quaternion) -> Quaternion
+    {
+        // The rotation axis is a pseudovector, so it gets negated when changing handedness
+
+        let axis = self.convert_point(to, &[q.i, q.j, q.k]);
+        let sign = if self.handedness() == to.handedness() { 1.0 } else { -1.0 };
+
+        Quaternion { w: q.w, i: sign * axis[0], j: sign * axis[1], k: sign * axis[2] }
+    }
+
+    /// Convert a rigid motion from this convention to `to`.
+    /// Only works on normalized dual quaternions.
+    pub fn convert_dual_quaternion(&self, to: &Convention, dq: &DualQuaternion) -> DualQuaternion
+    {
+        let r = self.convert_quaternion(to, &dq.rotation());
+        let t = self.convert_point(to, &dq.translation());
+
+        let rotor = DualQuaternion { w: r.w, i: r.i, j: r.j, k: r.k, ..DualQuaternion::ZERO };
+
+        DualQuaternion::translator(&t) * rotor
+    }
+}
@@ -0,0 +1,182 @@
+//! Dual complex numbers are the 2D analogue of dual quaternions and can be used for rigid motions in the plane.
+//!
+//! They are just the dual quaternions which rotate around the z-axis and translate in the xy-plane,
+//! i.e. the ones only having the components w, k, ie and je.
+
+pub use crate::dual_quaternion::DualQuaternion;
+pub use crate::angle::Angle;
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
+    derive_more::Neg, derive_more::From
+)]
+pub struct DualComplex
+{
+    pub w  : Scalar,
+    pub k  : Scalar,
+    pub ie : Scalar,
+    pub je : Scalar,
+}
+
+impl From<DualComplex> for DualQuaternion
+{
+    fn from(dc: DualComplex) -> Self
+    {
+        DualQuaternion { w: dc.w, k: dc.k, ie: dc.ie, je: dc.je, ..DualQuaternion::ZERO }
+    }
+}
+
+/// Drops all components which aren't part of a planar motion.
+impl From<DualQuaternion> for DualComplex
+{
+    fn from(dq: DualQuaternion) -> Self
+    {
+        DualComplex { w: dq.w, k: dq.k, ie: dq.ie, je: dq.je }
+    }
+}
+
+impl DualComplex
+{
+    pub const ZERO: Self = Self { w: 0.0, k: 0.0, ie: 0.0, je: 0.0 };
+    pub const ONE:  Self = Self { w: 1.0, ..Self::ZERO };
+
+    pub fn new(w: Scalar, k: Scalar, ie: Scalar, je: Scalar) -> Self
+    {
+        DualComplex { w, k, ie, je }
+    }
+
+    /// Negate everything except the scalar part.
+    /// For normalized dual complex numbers, this is the inverse.
+    pub fn conj(&self) -> Self
+    {
+        Self { w: self.w, k: -self.k, ie: -self.ie, je: -self.je }
+    }
+
+    /// The norm of the rotational part.
+    pub fn norm(&self) -> Scalar
+    {
+        (self.w * self.w + self.k * self.k).sqrt()
+    }
+
+    /// Normalize this DualComplex by its rotational part.
+    pub fn normalized(&self) -> Self
+    {
+        *self * (1.0 / self.norm())
+    }
+
+    /// Counter-clockwise rotation around the origin.
+    pub fn rotor(angle: Angle) -> Self
+    {
+        let (sin,cos) = (angle*0.5).sin_cos();
+
+        DualComplex { w: cos, k: sin, ..Self::ZERO }
+    }
+
+    /// Translational DualComplex.
+    pub fn translator(translation: &[Scalar]) -> Self
+    {
+        DualComplex { w: 1.0, k: 0.0, ie: 0.5 * translation[0], je: 0.5 * translation[1] }
+    }
+
+    /// The angle this motion rotates by.
+    pub fn angle(&self) -> Angle
+    {
+        Angle::radians(2.0 * self.k.atan2(self.w))
+    }
+
+    /// Where this motion moves the origin to.
+    /// Only works on normalized dual complex numbers.
+    pub fn translation(&self) -> [Scalar; 2]
+    {
+        self.transform_point2(&[0.0, 0.0])
+    }
+
+    /// Transform a 2D-vector as point, i.e. rotate and translate it.
+    pub fn transform_point2(&self, point: &[Scalar]) -> [Scalar; 2]
+    {
+        // Same as DualQuaternion::transform_point(), but most of the terms are zero
+
+        let DualComplex { w, k, ie, je } = *self;
+
+        let ax = ie - k * point[1];
+        let ay = je + k * point[0];
+
+        [
+            point[0] + 2.0 * (w * ax - k * ay),
+            point[1] + 2.0 * (w * ay + k * ax),
+        ]
+    }
+
+    /// Transform a 2D-vector as direction, i.e. only rotate it.
+    pub fn transform_vector2(&self, vector: &[Scalar]) -> [Scalar; 2]
+    {
+        let (w, k) = (self.w, self.k);
+
+        let ax = -k * vector[1];
+        let ay =  k * vector[0];
+
+        [
+            vector[0] + 2.0 * (w * ax - k * ay),
+            vector[1] + 2.0 * (w * ay + k * ax),
+        ]
+    }
+
+    /// Column-major 3x3 matrix of this motion in homogeneous coordinates, i.e. `matrix[column][row]`.
+    /// Only works on normalized dual complex numbers.
+    pub fn to_matrix3(&self) -> [[Scalar; 3]; 3]
+    {
+        let x = self.transform_vector2(&[1.0, 0.0]);
+        let y = self.transform_vector2(&[0.0, 1.0]);
+        let t = self.translation();
+
+        [
+            [x[0], x[1], 0.0],
+            [y[0], y[1], 0.0],
+            [t[0], t[1], 1.0],
+        ]
+    }
+
+    /// Linearily interpolate between `self` and `other`
+    pub fn lerp(&self, other: &DualComplex, alpha: Scalar) -> DualComplex
+    {
+        (1.0 - alpha) * self + alpha * other
+    }
+
+    /// Screw-lerp (in 2D this is rotating around a fixed point) between this and another dual complex number.
+    /// Only works on normalized dual complex numbers.
+    pub fn sclerp(&self, other: &DualComplex, alpha: Scalar) -> DualComplex
+    {
+        DualQuaternion::from(*self).sclerp(&(*other).into(), alpha).into()
+    }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &DualComplex, rhs: &DualComplex| -> DualComplex {
+    DualComplex {
+        w:  lhs.w * rhs.w  - lhs.k * rhs.k,
+        k:  lhs.w * rhs.k  + lhs.k * rhs.w,
+        ie: lhs.w * rhs.ie - lhs.k * rhs.je + lhs.ie * rhs.w + lhs.je * rhs.k,
+        je: lhs.w * rhs.je + lhs.k * rhs.ie + lhs.je * rhs.w - lhs.ie * rhs.k,
+    }
+});
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &DualComplex, rhs: &Scalar| -> DualComplex {
+    DualComplex {
+        w:  rhs * lhs.w,
+        k:  rhs * lhs.k,
+        ie: rhs * lhs.ie,
+        je: rhs * lhs.je,
+    }
+});
+
+auto_ops::impl_op_ex!(*= |lhs: &mut DualComplex, rhs: &Scalar| {
+    lhs.w  *= rhs;
+    lhs.k  *= rhs;
+    lhs.ie *= rhs;
+    lhs.je *= rhs;
+});
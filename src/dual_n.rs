@@ -0,0 +1,235 @@
+//! Dual numbers with several dual parts, i.e. `re + du[0] E0 + du[1] E1 + ...` where all products of E's are zero.
+//! Evaluating a function of N variables on them gives the full gradient at once,
+//! instead of one `DualNumber` pass per variable.
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+use core::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DualN<const N: usize>
+{
+    pub re: Scalar,
+    pub du: [Scalar; N],
+}
+
+impl<const N: usize> From<Scalar> for DualN<N>
+{
+    fn from(value: Scalar) -> Self { DualN::constant(value) }
+}
+
+impl<const N: usize> Default for DualN<N>
+{
+    fn default() -> Self { DualN::constant(0.0) }
+}
+
+impl<const N: usize> DualN<N>
+{
+    pub const fn new(re: Scalar, du: [Scalar; N]) -> Self
+    {
+        DualN { re, du }
+    }
+
+    /// A value not depending on any variable, i.e. all dual parts are 0.
+    pub const fn constant(re: Scalar) -> Self
+    {
+        DualN { re, du: [0.0; N] }
+    }
+
+    /// The `index`-th variable with the given value, i.e. its `index`-th dual part is 1.
+    pub fn variable(re: Scalar, index: usize) -> Self
+    {
+        let mut du = [0.0; N];
+        du[index] = 1.0;
+
+        DualN { re, du }
+    }
+
+    /// All `N` variables at the point `at`.
+    pub fn variables(at: [Scalar; N]) -> [Self; N]
+    {
+        core::array::from_fn(|i| Self::variable(at[i], i))
+    }
+
+    /// The partial derivatives with respect to all variables.
+    pub fn gradient(&self) -> [Scalar; N]
+    {
+        self.du
+    }
+
+    /// Apply a real function with value `value` and derivative `slope` at `self.re` (chain rule).
+    fn chain(&self, value: Scalar, slope: Scalar) -> Self
+    {
+        DualN { re: value, du: self.du.map(|d| d * slope) }
+    }
+
+    /// Multiplicative inverse, i.e. `1/self`.
+    /// May produce invalid numbers if .re is 0.0
+    pub fn recip(&self) -> Self
+    {
+        self.chain(1.0 / self.re, -1.0 / (self.re * self.re))
+    }
+
+    /// This may return invalid numbers if .re <= 0.0
+    pub fn sqrt(&self) -> Self
+    {
+        let s = self.re.sqrt();
+        self.chain(s, 0.5 / s)
+    }
+
+    pub fn exp(&self) -> Self
+    {
+        let e = self.re.exp();
+        self.chain(e, e)
+    }
+
+    /// Natural logarithm.
+    /// This may return invalid numbers if .re <= 0.0
+    pub fn log(&self) -> Self
+    {
+        self.chain(self.re.ln(), 1.0 / self.re)
+    }
+
+    pub fn sin(&self) -> Self
+    {
+        let (sin, cos) = self.re.sin_cos();
+        self.chain(sin, cos)
+    }
+
+    pub fn cos(&self) -> Self
+    {
+        let (sin, cos) = self.re.sin_cos();
+        self.chain(cos, -sin)
+    }
+
+    /// May produce invalid numbers when .re is an odd multiple of Pi/2
+    pub fn tan(&self) -> Self
+    {
+        self.chain(self.re.tan(), 1.0 / self.re.cos().powi(2))
+    }
+
+    /// Four quadrant arctangent of `self` (y) and `x`, like `Scalar::atan2()`.
+    /// The derivative is invalid if both real parts are 0.0
+    pub fn atan2(&self, x: &Self) -> Self
+    {
+        // d atan2(y, x) = (x dy - y dx) / (x^2 + y^2)
+        let n = x.re*x.re + self.re*self.re;
+
+        DualN {
+            re: self.re.atan2(x.re),
+            du: core::array::from_fn(|i| (x.re * self.du[i] - self.re * x.du[i]) / n),
+        }
+    }
+
+    /// Raise to some (real) power.
+    /// This may return invalid numbers if .re <= 0.0
+    pub fn powf(&self, f: Scalar) -> Self
+    {
+        self.chain(self.re.powf(f), f * self.re.powf(f - 1.0))
+    }
+
+    /// Raise to some integer power.
+    pub fn powi(&self, i: i32) -> Self
+    {
+        self.chain(self.re.powi(i), i as Scalar * self.re.powi(i - 1))
+    }
+}
+
+/// Gradient of `f` at `at`, e.g. `gradient(|[x, y]| x * y.sin(), [1.0, 2.0])`.
+pub fn gradient<const N: usize>(f: impl Fn([DualN<N>; N]) -> DualN<N>, at: [Scalar; N]) -> [Scalar; N]
+{
+    f(DualN::variables(at)).gradient()
+}
+
+impl<const N: usize> Add for DualN<N>
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self { DualN { re: self.re + rhs.re, du: core::array::from_fn(|i| self.du[i] + rhs.du[i]) } }
+}
+
+impl<const N: usize> Sub for DualN<N>
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self { DualN { re: self.re - rhs.re, du: core::array::from_fn(|i| self.du[i] - rhs.du[i]) } }
+}
+
+impl<const N: usize> Mul for DualN<N>
+{
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self
+    {
+        DualN { re: self.re * rhs.re, du: core::array::from_fn(|i| self.re * rhs.du[i] + self.du[i] * rhs.re) }
+    }
+}
+
+/// May produce invalid numbers if the real part of `rhs` is 0.0
+impl<const N: usize> Div for DualN<N>
+{
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self { Mul::mul(self, rhs.recip()) }
+}
+
+impl<const N: usize> Neg for DualN<N>
+{
+    type Output = Self;
+    fn neg(self) -> Self { DualN { re: -self.re, du: self.du.map(|d| -d) } }
+}
+
+impl<const N: usize> Add<Scalar> for DualN<N>
+{
+    type Output = Self;
+    fn add(self, rhs: Scalar) -> Self { DualN { re: self.re + rhs, du: self.du } }
+}
+
+impl<const N: usize> Sub<Scalar> for DualN<N>
+{
+    type Output = Self;
+    fn sub(self, rhs: Scalar) -> Self { DualN { re: self.re - rhs, du: self.du } }
+}
+
+impl<const N: usize> Mul<Scalar> for DualN<N>
+{
+    type Output = Self;
+    fn mul(self, rhs: Scalar) -> Self { DualN { re: self.re * rhs, du: self.du.map(|d| d * rhs) } }
+}
+
+impl<const N: usize> Div<Scalar> for DualN<N>
+{
+    type Output = Self;
+    fn div(self, rhs: Scalar) -> Self { DualN { re: self.re / rhs, du: self.du.map(|d| d / rhs) } }
+}
+
+impl<const N: usize> Add<DualN<N>> for Scalar
+{
+    type Output = DualN<N>;
+    fn add(self, rhs: DualN<N>) -> DualN<N> { rhs + self }
+}
+
+impl<const N: usize> Sub<DualN<N>> for Scalar
+{
+    type Output = DualN<N>;
+    fn sub(self, rhs: DualN<N>) -> DualN<N> { -rhs + self }
+}
+
+impl<const N: usize> Mul<DualN<N>> for Scalar
+{
+    type Output = DualN<N>;
+    fn mul(self, rhs: DualN<N>) -> DualN<N> { rhs * self }
+}
+
+impl<const N: usize> Div<DualN<N>> for Scalar
+{
+    type Output = DualN<N>;
+    fn div(self, rhs: DualN<N>) -> DualN<N> { DualN::constant(self) / rhs }
+}
+
+impl<const N: usize> AddAssign for DualN<N> { fn add_assign(&mut self, rhs: Self) { *self = *self + rhs } }
+impl<const N: usize> SubAssign for DualN<N> { fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs } }
+impl<const N: usize> MulAssign for DualN<N> { fn mul_assign(&mut self, rhs: Self) { *self = *self * rhs } }
+impl<const N: usize> DivAssign for DualN<N> { fn div_assign(&mut self, rhs: Self) { *self = *self / rhs } }
+impl<const N: usize> AddAssign<Scalar> for DualN<N> { fn add_assign(&mut self, rhs: Scalar) { self.re += rhs } }
+impl<const N: usize> SubAssign<Scalar> for DualN<N> { fn sub_assign(&mut self, rhs: Scalar) { self.re -= rhs } }
+impl<const N: usize> MulAssign<Scalar> for DualN<N> { fn mul_assign(&mut self, rhs: Scalar) { *self = *self * rhs } }
+impl<const N: usize> DivAssign<Scalar> for DualN<N> { fn div_assign(&mut self, rhs: Scalar) { *self = *self / rhs } }
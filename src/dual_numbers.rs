@@ -1,7 +1,9 @@
 //! Dual numbers are like complex numbers, but instead of i^2 = -1 we define E^2 = 0.
 //! They can be used for some niche applications and mechanical stuff
 
-use crate::util::Scalar;
+use crate::util::{Scalar, write_terms};
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
 
 #[repr(C)]
 #[derive(
@@ -25,23 +27,10 @@ impl From<&Scalar> for DualNumber
     fn from(value: &Scalar) -> Self { DualNumber { re: *value, du: 0.0 } }
 }
 
-impl std::fmt::Display for DualNumber
+impl core::fmt::Display for DualNumber
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.re.powi(2) > Scalar::EPSILON {
-            write!(f, "{}",  self.re);
-
-            if self.du.powi(2) > Scalar::EPSILON
-            {
-                write!(f, " + ");
-            }
-        }
-
-        if self.du.powi(2) > Scalar::EPSILON {
-            write!(f, "{}i", self.du);
-        }
-
-        write!(f, "")
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_terms(f, &[(self.re, ""), (self.du, "i")])
     }
 }
 
@@ -50,8 +39,10 @@ impl DualNumber
     pub const ZERO: DualNumber = DualNumber { re: 0.0, du: 0.0 };
     pub const ONE:  DualNumber = DualNumber { re: 1.0, du: 0.0 };
     pub const DUAL: DualNumber = DualNumber { re: 0.0, du: 1.0 };
+    /// The infinitesimal unit 0 + 1E (same as `DUAL`), e.g. for seeding derivatives with `x + DualNumber::EPSILON`
+    pub const EPSILON: DualNumber = DualNumber { re: 0.0, du: 1.0 };
 
-    pub fn new(re: Scalar, du: Scalar) -> Self
+    pub const fn new(re: Scalar, du: Scalar) -> Self
     {
         DualNumber { re, du }
     }
@@ -73,6 +64,38 @@ impl DualNumber
     /// Normalizes this DualNumber by the Euclidean Norm
     pub fn normalized(&self) -> Self { *self * (1.0 / self.norm()) }
 
+    /// Whether both parts are exactly 0.0 (unlike `seminorm() == 0.0`, which ignores the dual part)
+    pub fn is_zero(&self) -> bool
+    {
+        self.re == 0.0 && self.du == 0.0
+    }
+
+    /// Whether both parts are neither infinite nor NaN
+    pub fn is_finite(&self) -> bool
+    {
+        self.re.is_finite() && self.du.is_finite()
+    }
+
+    /// Whether both parts differ from `other`'s by at most `eps`
+    pub fn approx_eq(&self, other: &DualNumber, eps: Scalar) -> bool
+    {
+        (self.re - other.re).abs() <= eps && (self.du - other.du).abs() <= eps
+    }
+
+    /// Multiplicative inverse, i.e. `1/self`.
+    /// Returns `None` if .re == 0.0, since a purely dual number has no inverse.
+    pub fn try_inverse(&self) -> Option<Self>
+    {
+        // 1/(a+bE) = (a-bE) / a^2
+        (self.re != 0.0).then(|| self.conj() * (1.0 / (self.re*self.re)))
+    }
+
+    /// Division which returns `None` instead of inf/NaN if `rhs` has no inverse (.re == 0.0)
+    pub fn checked_div(&self, rhs: &DualNumber) -> Option<Self>
+    {
+        rhs.try_inverse().map(|inv| self * inv)
+    }
+
     /// This may return invalid numbers if .re <= 0.0
     pub fn sqrt(&self) -> Self
     {
@@ -155,6 +178,87 @@ impl DualNumber
         DualNumber { re: self.re.tan(), du: self.du / self.re.cos().powi(2) }
     }
 
+    /// Dual number arcsine function.
+    /// This may return invalid numbers if .re isn't in [-1,1], the derivative is infinite at .re = ±1
+    pub fn asin(&self) -> Self
+    {
+        // asin'(x) = 1 / sqrt(1 - x^2)
+        DualNumber { re: self.re.asin(), du: self.du / (1.0 - self.re*self.re).sqrt() }
+    }
+
+    /// Dual number arccosine function.
+    /// This may return invalid numbers if .re isn't in [-1,1], the derivative is infinite at .re = ±1
+    pub fn acos(&self) -> Self
+    {
+        // acos'(x) = -1 / sqrt(1 - x^2)
+        DualNumber { re: self.re.acos(), du: -self.du / (1.0 - self.re*self.re).sqrt() }
+    }
+
+    /// Dual number arctangent function
+    pub fn atan(&self) -> Self
+    {
+        // atan'(x) = 1 / (1 + x^2)
+        DualNumber { re: self.re.atan(), du: self.du / (1.0 + self.re*self.re) }
+    }
+
+    /// Four quadrant arctangent of `self` (y) and `x`, like `Scalar::atan2()`.
+    /// The derivative is invalid if both real parts are 0.0
+    pub fn atan2(&self, x: &DualNumber) -> Self
+    {
+        // d atan2(y, x) = (x dy - y dx) / (x^2 + y^2)
+        let y = self;
+        DualNumber {
+            re: y.re.atan2(x.re),
+            du: (x.re * y.du - y.re * x.du) / (x.re*x.re + y.re*y.re)
+        }
+    }
+
+    /// Dual number hyperbolic sine function
+    pub fn sinh(&self) -> Self
+    {
+        // f(a+bE) = f(a) + f'(a)bE
+        // sinh' = cosh
+        DualNumber { re: self.re.sinh(), du: self.re.cosh() * self.du }
+    }
+
+    /// Dual number hyperbolic cosine function
+    pub fn cosh(&self) -> Self
+    {
+        // cosh' = sinh
+        DualNumber { re: self.re.cosh(), du: self.re.sinh() * self.du }
+    }
+
+    /// Dual number hyperbolic tangent function
+    pub fn tanh(&self) -> Self
+    {
+        // tanh' = 1 - tanh^2
+        let t = self.re.tanh();
+        DualNumber { re: t, du: (1.0 - t*t) * self.du }
+    }
+
+    /// Dual number inverse hyperbolic sine function
+    pub fn asinh(&self) -> Self
+    {
+        // asinh'(x) = 1 / sqrt(x^2 + 1)
+        DualNumber { re: self.re.asinh(), du: self.du / (self.re*self.re + 1.0).sqrt() }
+    }
+
+    /// Dual number inverse hyperbolic cosine function.
+    /// This may return invalid numbers if .re < 1.0, the derivative is infinite at .re = 1.0
+    pub fn acosh(&self) -> Self
+    {
+        // acosh'(x) = 1 / sqrt(x^2 - 1)
+        DualNumber { re: self.re.acosh(), du: self.du / (self.re*self.re - 1.0).sqrt() }
+    }
+
+    /// Dual number inverse hyperbolic tangent function.
+    /// This may return invalid numbers if .re isn't in (-1,1)
+    pub fn atanh(&self) -> Self
+    {
+        // atanh'(x) = 1 / (1 - x^2)
+        DualNumber { re: self.re.atanh(), du: self.du / (1.0 - self.re*self.re) }
+    }
+
     /// Raise a DualNumber to some (real) power.
     /// This may return invalid numbers if .re <= 0.0
     pub fn powf(&self, f: Scalar) -> Self
@@ -162,6 +266,15 @@ impl DualNumber
         ( f * self.log() ).exp()
     }
 
+    /// Raise a DualNumber to a dual power, i.e. the exponent may be differentiated, too.
+    /// This may return invalid numbers if .re <= 0.0
+    pub fn powd(&self, exp: DualNumber) -> Self
+    {
+        // a^b = exp(b * log(a))
+        // => d(a^b) = a^b * (db * log(a) + b * da/a)
+        ( exp * self.log() ).exp()
+    }
+
     /// Raise a DualNumber to some integer power.
     /// This may return invalid numbers if .re <= 0.0
     pub fn powi(&self, i: i32) -> Self
@@ -193,6 +306,37 @@ impl DualNumber
 }
 
 
+/// Scalar functions which can be evaluated on dual numbers, which gives their derivatives (forward-mode autodiff).
+/// Implemented for all closures `Fn(DualNumber) -> DualNumber`.
+pub trait DualFn
+{
+    fn eval(&self, x: DualNumber) -> DualNumber;
+
+    /// The function's value and derivative at `at`.
+    fn value_and_derivative(&self, at: Scalar) -> (Scalar, Scalar)
+    {
+        // Seed the dual part with 1, i.e. dx/dx
+        let y = self.eval(at + DualNumber::EPSILON);
+        (y.re, y.du)
+    }
+
+    /// The function's derivative at `at`.
+    fn derivative(&self, at: Scalar) -> Scalar
+    {
+        self.value_and_derivative(at).1
+    }
+}
+
+impl<F: Fn(DualNumber) -> DualNumber> DualFn for F
+{
+    fn eval(&self, x: DualNumber) -> DualNumber { self(x) }
+}
+
+/// Derivative of `f` at `at`, e.g. `derivative(|x| x * x.sin(), 1.0)`.
+pub fn derivative(f: impl Fn(DualNumber) -> DualNumber, at: Scalar) -> Scalar
+{
+    f.derivative(at)
+}
 
 auto_ops::impl_op_ex!(* |lhs: &DualNumber, rhs: &DualNumber| -> DualNumber {
     DualNumber {
@@ -200,10 +344,7 @@ auto_ops::impl_op_ex!(* |lhs: &DualNumber, rhs: &DualNumber| -> DualNumber {
         du: lhs.re * rhs.du + lhs.du * rhs.re,
     }
 });
-auto_ops::impl_op_ex!(*= |lhs: &mut DualNumber, rhs: &DualNumber| {
-    lhs.re = lhs.re * rhs.re;
-    lhs.du = lhs.du * rhs.re + lhs.re * rhs.du;
-});
+auto_ops::impl_op_ex!(*= |lhs: &mut DualNumber, rhs: &DualNumber| { *lhs = *lhs * rhs });
 auto_ops::impl_op_ex_commutative!(* |lhs: &DualNumber, rhs: &Scalar| -> DualNumber {
     DualNumber
     {
@@ -216,6 +357,7 @@ auto_ops::impl_op_ex!(*= |lhs: &mut DualNumber, rhs: &Scalar| {
     lhs.du *= rhs;
 });
 
+// Dividing by a DualNumber with .re == 0.0 produces inf/NaN, use try_inverse() or checked_div() to catch that
 auto_ops::impl_op_ex!(/ |lhs: &DualNumber, rhs: &DualNumber| -> DualNumber { lhs * rhs.conj() * (1.0 / rhs.seminorm().powi(2) ) });
 auto_ops::impl_op_ex!(/= |lhs: &mut DualNumber, rhs: &DualNumber| { *lhs *= rhs.conj() * (1.0 / rhs.seminorm().powi(2) ) });
 auto_ops::impl_op_ex!(/ |lhs: &DualNumber, rhs: &Scalar| -> DualNumber {
@@ -225,6 +367,7 @@ auto_ops::impl_op_ex!(/ |lhs: &DualNumber, rhs: &Scalar| -> DualNumber {
         du: lhs.du / rhs
     }
 });
+// Same for 1.0 / DualNumber, see try_inverse()
 auto_ops::impl_op_ex!(/ |lhs: &Scalar, rhs: &DualNumber| -> DualNumber { lhs * rhs.conj() * (1.0 / rhs.seminorm().powi(2) ) });
 auto_ops::impl_op_ex!(/= |lhs: &mut DualNumber, rhs: &Scalar| {
     lhs.re /= rhs;
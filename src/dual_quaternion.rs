@@ -2,9 +2,13 @@
 
 pub use crate::quaternion::Quaternion;
 pub use crate::angle::Angle;
+pub use crate::line::Line;
 
+use crate::screw::Twist;
 use crate::vector3::Vector3;
-use crate::util::Scalar;
+use crate::util::{Scalar, write_terms};
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
 
 #[repr(C)]
 #[derive(
@@ -24,35 +28,13 @@ pub struct DualQuaternion
     pub we : Scalar,
 }
 
-impl std::fmt::Display for DualQuaternion
+impl core::fmt::Display for DualQuaternion
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let components = [
-            ("",   self.w),
-            ("i",  self.i),
-            ("j",  self.j),
-            ("k",  self.k),
-            ("ie", self.ie),
-            ("je", self.je),
-            ("ke", self.ke),
-            ("we", self.we),
-        ];
-
-        for (i,(c,v)) in components.iter().enumerate()
-        {
-            if v*v <= Scalar::EPSILON {
-                continue
-            }
-
-            write!(f, "{}{}", c, v);
-
-            if components[i+1..].iter().find(|x| x.1.powi(2) > Scalar::EPSILON).is_some()
-            {
-                write!(f, " + ");
-            }
-        }
-
-        write!(f, "")
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_terms(f, &[
+            (self.w, ""), (self.i, "i"), (self.j, "j"), (self.k, "k"),
+            (self.ie, "ie"), (self.je, "je"), (self.ke, "ke"), (self.we, "we"),
+        ])
     }
 }
 
@@ -71,6 +53,7 @@ impl DualQuaternion
     pub const DUAL_Z: Self = Self { ke:  1.0, ..Self::ZERO };
 
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(w: Scalar, i: Scalar, j: Scalar, k: Scalar,
                ie: Scalar, je: Scalar, ke: Scalar, we: Scalar) -> Self
     {
@@ -152,6 +135,116 @@ impl DualQuaternion
         *self * (1.0 / self.norm())
     }
 
+    /// Make this motor a rigid transformation again, i.e. a normalized real part orthogonal to the dual part.
+    /// Useful after many products, when rounding errors have piled up.
+    pub fn renormalized(&self) -> Self
+    {
+        let DualQuaternion { w, i, j, k, ie, je, ke, we } = self.normalized();
+
+        // Remove the part of the dual quaternion which is parallel to the real one
+        let d = w*we + i*ie + j*je + k*ke;
+
+        DualQuaternion { w, i, j, k, ie: ie - d*i, je: je - d*j, ke: ke - d*k, we: we - d*w }
+    }
+
+    /// The rotational part of this motor, i.e. the real-part-Quaternion.
+    pub fn rotation(&self) -> Quaternion
+    {
+        Quaternion { w: self.w, i: self.i, j: self.j, k: self.k }
+    }
+
+    /// The translational part of this motor, i.e. where the origin gets moved to.
+    /// Only works on normalized dual quaternions.
+    pub fn translation(&self) -> [Scalar; 3]
+    {
+        // If this motor is T*R with T = 1 + 0.5tE, then the dual part is 0.5tR.
+        // So t = 2 * dual * ~R
+
+        let real = self.rotation();
+        let dual = Quaternion { w: self.we, i: self.ie, j: self.je, k: self.ke };
+
+        let t = 2.0 * dual * real.conj();
+
+        [t.i, t.j, t.k]
+    }
+
+    /// Weighted distance between the poses of two motors.
+    /// The rotational distance is the angle (in radians) of the rotation taking one pose to the other,
+    /// the translational distance is the Euclidean distance between the translations.
+    /// Only works on normalized dual quaternions.
+    pub fn distance_to(&self, other: &DualQuaternion, rotation_weight: Scalar, translation_weight: Scalar) -> Scalar
+    {
+        // Rotation taking one orientation to the other.
+        // q and -q are the same rotation, so take the absolute value of the scalar part.
+        // atan2 is more precise than acos for small angles.
+        let q = other.rotation() * self.rotation().conj();
+        let angle = 2.0 * (q.i*q.i + q.j*q.j + q.k*q.k).sqrt().atan2(q.w.abs());
+
+        let (t1,t2) = (self.translation(), other.translation());
+        let distance = (
+            (t1[0]-t2[0]).powi(2) +
+            (t1[1]-t2[1]).powi(2) +
+            (t1[2]-t2[2]).powi(2)
+        ).sqrt();
+
+        rotation_weight * angle + translation_weight * distance
+    }
+
+    /// Return this motor or its negation, whichever lies in the same hemisphere as `reference`.
+    /// Both describe the same motion, but blending motors of opposite signs gives wrong results.
+    pub fn aligned_with(&self, reference: &DualQuaternion) -> Self
+    {
+        let dot = self.w*reference.w + self.i*reference.i + self.j*reference.j + self.k*reference.k;
+
+        if dot < 0.0 { -*self } else { *self }
+    }
+
+    /// Align all motors with the hemisphere of the first one, see `DualQuaternion::aligned_with()`.
+    /// Use this on a set of motors (e.g. a bone palette) before blending them.
+    pub fn align_hemispheres(motors: &mut [DualQuaternion])
+    {
+        let Some(&reference) = motors.first() else { return };
+
+        for motor in motors.iter_mut()
+        {
+            *motor = motor.aligned_with(&reference);
+        }
+    }
+
+    /// The pose of `b` relative to `a`, i.e. the motor with `a * relative(a, b) = b`.
+    /// Only works on normalized dual quaternions.
+    pub fn relative(a: &DualQuaternion, b: &DualQuaternion) -> Self
+    {
+        // For normalized motors the conjugate is the inverse
+        a.conj() * b
+    }
+
+    /// Compose a chain of relative poses, e.g. `[world_T_a, a_T_b, b_T_c]` to `world_T_c`.
+    /// The inverse of `DualQuaternion::relative()` applied along the chain.
+    pub fn compose_chain(motors: &[DualQuaternion]) -> Self
+    {
+        motors.iter().fold(DualQuaternion::ONE, |acc, motor| acc * motor)
+    }
+
+    /// Multiplicative inverse of this dual quaternion.
+    /// May produce invalid numbers if the real-part-Quaternion is 0.0
+    pub fn inverse(&self) -> Self
+    {
+        // (r + dE)^-1 = r^-1 - r^-1 d r^-1 E
+        // You can check this by multiplying it with r + dE and using E² = 0
+
+        let real = self.rotation();
+        let dual = Quaternion { w: self.we, i: self.ie, j: self.je, k: self.ke };
+
+        let real_inv = 1.0 / real;
+        let dual_inv = -(real_inv * dual * real_inv);
+
+        DualQuaternion {
+            w: real_inv.w, i: real_inv.i, j: real_inv.j, k: real_inv.k,
+            ie: dual_inv.i, je: dual_inv.j, ke: dual_inv.k, we: dual_inv.w
+        }
+    }
+
     /// Create a DualQuaternion representing a point in space, i.e. 1+(xi + yj + zk)E.
     pub fn point(pos: &[Scalar]) -> Self
     {
@@ -171,26 +264,20 @@ impl DualQuaternion
     /// Also, r will be normalized.
     pub fn line(pos: &[Scalar], dir: &[Scalar]) -> Self
     {
-        let n = 1.0 / (dir[0]*dir[0] + dir[1]*dir[1] + dir[2]*dir[2]).sqrt();
-        let dir = [
-            dir[0] * n,
-            dir[1] * n,
-            dir[2] * n,
-        ];
-
-        // normalized cross product between pos and dir
-        let moment = [
-            n * (pos[1] * dir[2] - dir[1] * pos[2]),
-            n * (pos[2] * dir[0] - dir[2] * pos[0]),
-            n * (pos[0] * dir[1] - dir[0] * pos[1]),
-        ];
+        Line::new(pos, dir).into()
+    }
 
-        DualQuaternion { w: 0.0, i: dir[0], j: dir[1], k: dir[2], ie: moment[0], je: moment[1], ke: moment[2], we: 0.0 }
+    /// Create a screw around a line given as a DualQuaternion, see `DualQuaternion::from_screw()`.
+    /// Only the vector parts of `line` are used.
+    pub fn screw(line: &DualQuaternion, angle: Angle, distance: Scalar) -> Self
+    {
+        Self::from_screw(&Line::from(*line), angle, distance)
     }
 
     /// Create a screw around a line.
     /// The screw will rotate `angle` and travel `distance` units along the line.
-    pub fn screw(line: &DualQuaternion, angle: Angle, distance: Scalar) -> Self
+    /// The line's direction doesn't need to be normalized.
+    pub fn from_screw(line: &Line, angle: Angle, distance: Scalar) -> Self
     {
         // So you probably heard of exp(ix) for some real x being some kind of rotational thing.
         // If you multiply some complex number by exp(ix) then it gets rotated.
@@ -207,7 +294,7 @@ impl DualQuaternion
         //
         // This algorithm below shows you how to do it exactly.
 
-        let line = line.normalized();
+        let line = DualQuaternion::from(*line).normalized();
         let angle = 0.5 * angle.rad();
         let distance = 0.5 * distance;
 
@@ -222,7 +309,7 @@ impl DualQuaternion
             we: 0.0,
         };
 
-        return dq.exp();
+        dq.exp()
     }
 
     /// Basically a Quaternion
@@ -264,6 +351,87 @@ impl DualQuaternion
         }
     }
 
+    /// Camera-to-world motor of a camera at `eye` looking at `target`, with `up` pointing roughly upwards.
+    /// Like in OpenGL, the camera looks along its negative z-axis and its y-axis points up.
+    /// Use `.conj()` on the result to get the world-to-camera (view) motor.
+    /// `up` mustn't be parallel to the viewing direction.
+    pub fn look_at(eye: &[Scalar], target: &[Scalar], up: &[Scalar]) -> Self
+    {
+        let eye    = Vector3 { x: eye[0],    y: eye[1],    z: eye[2] };
+        let target = Vector3 { x: target[0], y: target[1], z: target[2] };
+        let up     = Vector3 { x: up[0],     y: up[1],     z: up[2] };
+
+        // The camera's axes in world coordinates are the columns of its rotation matrix
+        let z = (eye - target).normalize();
+        let x = up.cross(&z).normalize();
+        let y = z.cross(&x);
+
+        let Quaternion { w, i, j, k } = Quaternion::from_rotation_columns(x, y, z);
+
+        DualQuaternion::translator(&[eye.x, eye.y, eye.z]) * DualQuaternion { w, i, j, k, ..DualQuaternion::ZERO }
+    }
+
+    /// Create a motor from a column-major 4x4 matrix (i.e. `matrix[column][row]`) which is only
+    /// approximately rigid, e.g. due to accumulated floating point errors or a baked scale of about 1.
+    ///
+    /// The upper 3x3 part gets projected onto the nearest rotation matrix first.
+    /// Matrices containing reflections (negative determinant) will produce wrong results.
+    pub fn from_matrix4_renormalized(matrix: &[[Scalar; 4]; 4]) -> Self
+    {
+        let column = |c: usize| Vector3 { x: matrix[c][0], y: matrix[c][1], z: matrix[c][2] };
+
+        let (mut x, mut y, mut z) = (column(0), column(1), column(2));
+
+        // Polar decomposition by iterating M <- (M + M^-T) / 2, which converges to the nearest
+        // rotation matrix. The inverse transpose is the cofactor matrix divided by the determinant,
+        // whose columns are just cross products of the columns of M.
+        for _ in 0..16
+        {
+            let (cx, cy, cz) = (y.cross(&z), z.cross(&x), x.cross(&y));
+            let det = x.dot(&cx);
+
+            let (nx, ny, nz) = (
+                0.5 * (x + cx * (1.0 / det)),
+                0.5 * (y + cy * (1.0 / det)),
+                0.5 * (z + cz * (1.0 / det)),
+            );
+
+            let change = (nx - x).norm() + (ny - y).norm() + (nz - z).norm();
+            (x, y, z) = (nx, ny, nz);
+
+            if change < Scalar::EPSILON {
+                break
+            }
+        }
+
+        let rotation = Quaternion::from_rotation_columns(x, y, z);
+        let translation = [matrix[3][0], matrix[3][1], matrix[3][2]];
+
+        let rotation = DualQuaternion {
+            w: rotation.w, i: rotation.i, j: rotation.j, k: rotation.k,
+            ..DualQuaternion::ZERO
+        };
+
+        DualQuaternion::translator(&translation) * rotation
+    }
+
+    /// Column-major 4x4 matrix (i.e. `matrix[column][row]`) of this motor in homogeneous coordinates.
+    /// Only works on normalized dual quaternions.
+    pub fn to_matrix4(&self) -> [[Scalar; 4]; 4]
+    {
+        let x = self.transform_vector3(&[1.0, 0.0, 0.0]);
+        let y = self.transform_vector3(&[0.0, 1.0, 0.0]);
+        let z = self.transform_vector3(&[0.0, 0.0, 1.0]);
+        let t = self.translation();
+
+        [
+            [x[0], x[1], x[2], 0.0],
+            [y[0], y[1], y[2], 0.0],
+            [z[0], z[1], z[2], 0.0],
+            [t[0], t[1], t[2], 1.0],
+        ]
+    }
+
     /// Transform a 3D-vector as point.
     /// This means that the vector will be screwed around a line.
     pub fn transform_point(&self, point: &[Scalar]) -> [Scalar; 3]
@@ -282,10 +450,51 @@ impl DualQuaternion
         let vw = self.w;
         let mw = self.we;
 
-        let p: Vector3 = point.into();
-        let a = v.cross(&p) + m;
+        let a = v.cross(&point) + m;
 
-        (p + 2.0 * (vw*a + v.cross(&a) - mw*v)).into()
+        (point + 2.0 * (vw*a + v.cross(&a) - mw*v)).into()
+    }
+
+    /// Transform many 3D-vectors as points, see `DualQuaternion::transform_point()`.
+    pub fn transform_points_in_place(&self, points: &mut [[Scalar; 3]])
+    {
+        for point in points.iter_mut()
+        {
+            *point = self.transform_point(point);
+        }
+    }
+
+    /// Same as `DualQuaternion::transform_points_in_place()`, but splits the points across threads.
+    /// Only worth it for large amounts of points.
+    #[cfg(feature = "rayon")]
+    pub fn par_transform_points_in_place(&self, points: &mut [[Scalar; 3]])
+    {
+        use rayon::prelude::*;
+
+        points.par_chunks_mut(4096).for_each(|chunk| self.transform_points_in_place(chunk));
+    }
+
+    /// Transform the vertices of an interleaved vertex buffer in place, without copying them out first.
+    /// Every vertex takes `stride` scalars, its position starts at `position_offset` and its normal (if any) at `normal_offset`.
+    /// Positions are transformed as points, normals only get rotated. A trailing incomplete vertex is left alone.
+    /// Panics if the position or normal doesn't fit into the stride.
+    pub fn transform_strided(&self, data: &mut [Scalar], stride: usize, position_offset: usize, normal_offset: Option<usize>)
+    {
+        assert!(position_offset + 3 <= stride, "position doesn't fit into the vertex stride");
+        assert!(normal_offset.is_none_or(|offset| offset + 3 <= stride), "normal doesn't fit into the vertex stride");
+
+        for vertex in data.chunks_exact_mut(stride)
+        {
+            let position = &mut vertex[position_offset..position_offset + 3];
+            let transformed = self.transform_point(position);
+            position.copy_from_slice(&transformed);
+
+            if let Some(offset) = normal_offset {
+                let normal = &mut vertex[offset..offset + 3];
+                let transformed = self.transform_vector3(normal);
+                normal.copy_from_slice(&transformed);
+            }
+        }
     }
 
     /// Transform a 3D-vector as vector3.
@@ -367,27 +576,27 @@ impl DualQuaternion
 
         let r = (i*i + j*j + k*k).sqrt();
 
-        // exp(0.0) = 1
-        // Without this check, it won't work
-        if r*r < Scalar::EPSILON {
-            return DualQuaternion::ONE
-        }
-
         let t = i*ie + j*je + k*ke;
 
-        let (sin,cos) = r.sin_cos();
+        // For (almost) pure translations, use the power series of sin(r)/r, cos(r) and tr.
+        // Without this check, we would divide by zero, e.g. exp(εm) = 1 + εm, since ε² = 0.
+        let (sinc, cos, tr) = if r*r < Scalar::EPSILON {
+            (1.0 - r*r / 6.0, 1.0 - r*r / 2.0, -t / 3.0)
+        } else {
+            let (sin,cos) = r.sin_cos();
 
-        let tr = (cos/(r*r) - sin/(r*r*r))*t;
+            (sin/r, cos, (cos/(r*r) - sin/(r*r*r))*t)
+        };
 
         DualQuaternion {
             w:   cos,
-            i:   (sin/r) * i,
-            j:   (sin/r) * j,
-            k:   (sin/r) * k,
-            ie:  (sin/r) * ie + tr * i,
-            je:  (sin/r) * je + tr * j,
-            ke:  (sin/r) * ke + tr * k,
-            we: -(sin/r) * t
+            i:   sinc * i,
+            j:   sinc * j,
+            k:   sinc * k,
+            ie:  sinc * ie + tr * i,
+            je:  sinc * je + tr * j,
+            ke:  sinc * ke + tr * k,
+            we: -sinc * t
         }
     }
 
@@ -405,6 +614,24 @@ impl DualQuaternion
         let r = (i*i + j*j + k*k).sqrt();
         let t = i*ie + j*je + k*ke;
 
+        // (Almost) pure translation, i.e. log(1 + εm) = εm.
+        // Without this check, we would divide by zero. For small r, a is 1/w up to second order
+        // and (w - a)*b is -t/w, because w² + r² = 1.
+        if r*r < Scalar::EPSILON {
+            let tr = -t/w - we;
+
+            return DualQuaternion {
+                w:  0.0,
+                i:  i / w,
+                j:  j / w,
+                k:  k / w,
+                ie: ie / w + tr * i,
+                je: je / w + tr * j,
+                ke: ke / w + tr * k,
+                we: 0.0
+            }
+        }
+
         let a = (r/w).atan() / r;
         let b = t / (r*r);
 
@@ -429,6 +656,17 @@ impl DualQuaternion
         ( f * self.log() ).exp()
     }
 
+    /// The n-th root of this dual quaternion, i.e. the motor which applied `n` times yields this one.
+    /// Useful for subdividing a screw motion into `n` equal steps.
+    /// Will produce incorrect result for unnormalized dual quaternions.
+    /// Panics if `n` is 0.
+    pub fn root(&self, n: u32) -> DualQuaternion
+    {
+        assert!(n != 0, "there is no 0th root");
+
+        self.powf(1.0 / n as Scalar)
+    }
+
     /// Screw-lerp this dual quaternion between another dual quaternion.
     /// Only works on normalized dual quaternions.
     pub fn sclerp(&self, other: &DualQuaternion, alpha: Scalar) -> DualQuaternion
@@ -438,6 +676,23 @@ impl DualQuaternion
 
         self * (self.conj() * other).powf(alpha)
     }
+
+    /// Interpolate from `p0` to `p1` at `t` in [0,1], leaving `p0` with the velocity `v0` and arriving at `p1` with `v1`.
+    /// The twists are given in the body frames of the poses and in units per whole interval,
+    /// so multiply them by the interval's duration if they are per second.
+    /// Only works on normalized dual quaternions.
+    pub fn hermite(p0: &DualQuaternion, v0: &Twist, p1: &DualQuaternion, v1: &Twist, t: Scalar) -> DualQuaternion
+    {
+        // Same as for cubic curves through points: the Bézier control points lie a third of the velocity away
+        // from the end points. Body frame twists are applied from the right.
+
+        let p1 = p1.aligned_with(p0);
+
+        let b1 = p0 * v0.exp(1.0 / 3.0);
+        let b2 = p1 * v1.exp(-1.0 / 3.0);
+
+        crate::spline::bezier(&[*p0, b1, b2, p1], t)
+    }
 }
 
 auto_ops::impl_op_ex!(* |lhs: &DualQuaternion, rhs: &DualQuaternion| -> DualQuaternion {
@@ -482,3 +737,28 @@ auto_ops::impl_op_ex!(*= |lhs: &mut DualQuaternion, rhs: &Scalar| {
     lhs.ke = lhs.ke * rhs;
     lhs.we = lhs.we * rhs;
 });
+
+auto_ops::impl_op_ex!(/ |lhs: &DualQuaternion, rhs: &DualQuaternion| -> DualQuaternion { lhs * rhs.inverse() });
+auto_ops::impl_op_ex!(/ |lhs: &DualQuaternion, rhs: &Scalar| -> DualQuaternion {
+    DualQuaternion {
+        w:  lhs.w  / rhs,
+        i:  lhs.i  / rhs,
+        j:  lhs.j  / rhs,
+        k:  lhs.k  / rhs,
+        ie: lhs.ie / rhs,
+        je: lhs.je / rhs,
+        ke: lhs.ke / rhs,
+        we: lhs.we / rhs
+    }
+});
+auto_ops::impl_op_ex!(/ |lhs: &Scalar, rhs: &DualQuaternion| -> DualQuaternion { lhs * rhs.inverse() });
+auto_ops::impl_op_ex!(/= |lhs: &mut DualQuaternion, rhs: &Scalar| {
+    lhs.w  /= rhs;
+    lhs.i  /= rhs;
+    lhs.j  /= rhs;
+    lhs.k  /= rhs;
+    lhs.ie /= rhs;
+    lhs.je /= rhs;
+    lhs.ke /= rhs;
+    lhs.we /= rhs;
+});
@@ -0,0 +1,121 @@
+//! Dual vectors, i.e. `real + E dual` with 3D vectors `real` and `dual` and E^2 = 0.
+//! They represent lines (direction and moment) and screw quantities (e.g. twists) and
+//! their dot and cross products are the dual versions of the usual ones.
+
+pub use crate::dual_quaternion::DualQuaternion;
+pub use crate::dual_numbers::DualNumber;
+pub use crate::line::Line;
+pub use crate::screw::Twist;
+pub use crate::vector3::Vector3;
+
+use crate::util::Scalar;
+
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
+    derive_more::Neg
+)]
+pub struct DualVector3
+{
+    pub real: Vector3,
+    pub dual: Vector3,
+}
+
+/// Direction as real and moment as dual part.
+impl From<Line> for DualVector3
+{
+    fn from(line: Line) -> Self { DualVector3 { real: line.dir.into(), dual: line.moment.into() } }
+}
+
+impl From<DualVector3> for Line
+{
+    fn from(v: DualVector3) -> Self { Line { dir: v.real.into(), moment: v.dual.into() } }
+}
+
+/// Angular velocity as real and linear velocity as dual part.
+impl From<Twist> for DualVector3
+{
+    fn from(twist: Twist) -> Self { DualVector3 { real: twist.angular.into(), dual: twist.linear.into() } }
+}
+
+impl From<DualVector3> for Twist
+{
+    fn from(v: DualVector3) -> Self { Twist { angular: v.real.into(), linear: v.dual.into() } }
+}
+
+/// The vector parts of a dual quaternion, the same way lines are embedded.
+impl From<DualVector3> for DualQuaternion
+{
+    fn from(v: DualVector3) -> Self
+    {
+        let (r, d) = (v.real, v.dual);
+
+        DualQuaternion { w: 0.0, i: r.x, j: r.y, k: r.z, ie: d.x, je: d.y, ke: d.z, we: 0.0 }
+    }
+}
+
+/// Drops the scalar parts `w` and `we`.
+impl From<DualQuaternion> for DualVector3
+{
+    fn from(q: DualQuaternion) -> Self
+    {
+        DualVector3 { real: Vector3 { x: q.i, y: q.j, z: q.k }, dual: Vector3 { x: q.ie, y: q.je, z: q.ke } }
+    }
+}
+
+impl DualVector3
+{
+    pub const ZERO: DualVector3 = DualVector3 { real: Vector3::ZERO, dual: Vector3::ZERO };
+
+    pub fn new(real: Vector3, dual: Vector3) -> Self
+    {
+        DualVector3 { real, dual }
+    }
+
+    /// Dual dot product. For two lines with unit directions it's `cos(a) - E d sin(a)`,
+    /// where a is the angle and d the distance between them.
+    pub fn dot(&self, other: &Self) -> DualNumber
+    {
+        DualNumber {
+            re: self.real.dot(&other.real),
+            du: self.real.dot(&other.dual) + self.dual.dot(&other.real),
+        }
+    }
+
+    /// Dual cross product. For two lines it's their common normal line, scaled by `sin(a) + E d cos(a)`.
+    pub fn cross(&self, other: &Self) -> Self
+    {
+        DualVector3 {
+            real: self.real.cross(&other.real),
+            dual: self.real.cross(&other.dual) + self.dual.cross(&other.real),
+        }
+    }
+
+    /// Dual norm `|real| + E (real·dual)/|real|`.
+    /// May produce invalid numbers if the real part is zero
+    pub fn norm(&self) -> DualNumber
+    {
+        let n = self.real.norm();
+
+        DualNumber { re: n, du: self.real.dot(&self.dual) / n }
+    }
+
+    /// Divide by the dual norm, so the real part is a unit vector perpendicular to the dual part, e.g. a normalized line.
+    pub fn normalized(&self) -> Self
+    {
+        let n = self.norm();
+        let real = self.real / n.re;
+
+        // (r + Ed) / (a + Eb) = r/a + E (d/a - r b/a^2)
+        DualVector3 { real, dual: self.dual / n.re - (n.du / n.re) * real }
+    }
+}
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &DualVector3, rhs: &Scalar| -> DualVector3 {
+    DualVector3 { real: lhs.real * rhs, dual: lhs.dual * rhs }
+});
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &DualVector3, rhs: &DualNumber| -> DualVector3 {
+    DualVector3 { real: lhs.real * rhs.re, dual: lhs.dual * rhs.re + lhs.real * rhs.du }
+});
@@ -0,0 +1,154 @@
+//! Euler angles together with the order they are applied in, so a triple of angles can't be
+//! interpreted in the wrong order by accident.
+//!
+//! Roll rotates around the x-axis, pitch around the y-axis and yaw around the z-axis.
+
+pub use crate::quaternion::{Quaternion, Angle};
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+/// Order of intrinsic rotations, e.g. `ZYX` first rotates around z (yaw), then around the
+/// rotated y-axis (pitch) and then around the twice rotated x-axis (roll).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EulerOrder
+{
+    XYZ, XZY,
+    YXZ, YZX,
+    ZXY, ZYX,
+}
+
+impl EulerOrder
+{
+    /// Indices of the axes in the order they get applied.
+    fn axes(&self) -> [usize; 3]
+    {
+        match self
+        {
+            EulerOrder::XYZ => [0, 1, 2],
+            EulerOrder::XZY => [0, 2, 1],
+            EulerOrder::YXZ => [1, 0, 2],
+            EulerOrder::YZX => [1, 2, 0],
+            EulerOrder::ZXY => [2, 0, 1],
+            EulerOrder::ZYX => [2, 1, 0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EulerAngles
+{
+    pub yaw:   Angle,
+    pub pitch: Angle,
+    pub roll:  Angle,
+    pub order: EulerOrder,
+}
+
+impl From<EulerAngles> for Quaternion
+{
+    fn from(euler: EulerAngles) -> Self { euler.to_quaternion() }
+}
+
+impl EulerAngles
+{
+    pub fn new(yaw: Angle, pitch: Angle, roll: Angle, order: EulerOrder) -> Self
+    {
+        EulerAngles { yaw, pitch, roll, order }
+    }
+
+    /// The angle around the axis with index `axis` (0 = x, 1 = y, 2 = z).
+    fn angle(&self, axis: usize) -> Angle
+    {
+        [self.roll, self.pitch, self.yaw][axis]
+    }
+
+    /// The rotor rotating like these Euler angles.
+    pub fn to_quaternion(&self) -> Quaternion
+    {
+        // Intrinsic rotations a, b, c are the same as the product q_a * q_b * q_c
+
+        self.order.axes()
+            .map(|axis| {
+                let mut dir = [0.0; 3];
+                dir[axis] = 1.0;
+
+                Quaternion::rotor(self.angle(axis), &dir)
+            })
+            .into_iter()
+            .fold(Quaternion::ONE, |acc, q| acc * q)
+    }
+
+    /// Extract Euler angles applied in `order` from a rotor.
+    /// The middle rotation will be in [-90°,90°], the others in (-180°,180°].
+    /// At ±90° for the middle rotation (gimbal lock), only the sum or difference of the other two angles is defined,
+    /// then the last one is 0 and the first one gets the whole rotation.
+    pub fn from_quaternion(q: &Quaternion, order: EulerOrder) -> Self
+    {
+        // The columns of the rotation matrix are the rotated axes
+        let columns = [
+            q.transform_vector(&[1.0, 0.0, 0.0]),
+            q.transform_vector(&[0.0, 1.0, 0.0]),
+            q.transform_vector(&[0.0, 0.0, 1.0]),
+        ];
+        let m = |row: usize, col: usize| columns[col][row];
+
+        let [a, b, c] = order.axes();
+
+        // Cyclic orders (XYZ, YZX, ZXY) have different signs than the other ones
+        let s: Scalar = if (b + 3 - a) % 3 == 1 { 1.0 } else { -1.0 };
+
+        // More precise than asin(s * m(a, c)) near ±90°
+        let angle_b = (s * m(a, c)).atan2(m(a, a).hypot(m(a, b)));
+
+        let (angle_a, angle_c) = if m(a, c).abs() < 1.0 - 1e-6 {
+            ((-s * m(b, c)).atan2(m(c, c)), (-s * m(a, b)).atan2(m(a, a)))
+        }
+        else {
+            // Gimbal lock: the first and last axis coincide, so only the sum or difference of their angles is defined.
+            // Choose the last angle as 0, then the middle axis is only rotated by the first rotation.
+            ((s * m(c, b)).atan2(m(b, b)), 0.0)
+        };
+
+        let mut angles = [Angle::ZERO; 3];
+        angles[a] = Angle::radians(angle_a);
+        angles[b] = Angle::radians(angle_b);
+        angles[c] = Angle::radians(angle_c);
+
+        EulerAngles { roll: angles[0], pitch: angles[1], yaw: angles[2], order }
+    }
+
+    /// Same rotation, but with angles applied in another order.
+    pub fn with_order(&self, order: EulerOrder) -> Self
+    {
+        Self::from_quaternion(&self.to_quaternion(), order)
+    }
+
+    /// Wrap all angles into (-180°,180°].
+    pub fn wrapped(&self) -> Self
+    {
+        EulerAngles {
+            yaw:   self.yaw.wrapped_symmetric(),
+            pitch: self.pitch.wrapped_symmetric(),
+            roll:  self.roll.wrapped_symmetric(),
+            order: self.order
+        }
+    }
+
+    /// Interpolate between two orientations along the shortest arc.
+    /// The result uses the order of `self`.
+    pub fn lerp(&self, other: &EulerAngles, alpha: Scalar) -> Self
+    {
+        // Interpolating the angles independently would give a different path for every order,
+        // so interpolate the rotations instead
+
+        let (q1, mut q2) = (self.to_quaternion(), other.to_quaternion());
+
+        let dot = q1.w*q2.w + q1.i*q2.i + q1.j*q2.j + q1.k*q2.k;
+        if dot < 0.0 {
+            q2 = -q2;
+        }
+
+        Self::from_quaternion(&q1.slerp(q2, alpha), self.order)
+    }
+}
@@ -0,0 +1,79 @@
+//! Flectors are improper rigid motions, i.e. reflections and rotoreflections.
+//! Together with DualQuaternions (proper rigid motions) they cover all rigid motions.
+
+use crate::dual_quaternion::{DualQuaternion, Angle};
+use crate::plane::Plane;
+use crate::util::Scalar;
+
+/// An improper rigid motion.
+///
+/// Every improper rigid motion can be written as a reflection through the origin (p -> -p) followed by a
+/// proper rigid motion. This type stores that proper rigid motion as `motor`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Flector
+{
+    pub motor: DualQuaternion,
+}
+
+impl Flector
+{
+    /// Reflection through the origin, i.e. p -> -p.
+    pub const INVERSION: Flector = Flector { motor: DualQuaternion::ONE };
+
+    /// Mirror across a plane.
+    pub fn reflection(plane: &Plane) -> Self
+    {
+        // Reflecting -p across a plane through the origin is the same as
+        // rotating p by 180° around its normal. Then move it by the plane's offset (twice).
+
+        let n = plane.normal;
+        let d = 2.0 * plane.offset;
+
+        Flector {
+            motor: DualQuaternion::translator(&[d * n[0], d * n[1], d * n[2]]) *
+                   DualQuaternion::rotor(Angle::HALF, &n)
+        }
+    }
+
+    /// Mirror through a point, i.e. p -> 2c - p.
+    pub fn point_reflection(center: &[Scalar]) -> Self
+    {
+        Flector {
+            motor: DualQuaternion::translator(&[2.0 * center[0], 2.0 * center[1], 2.0 * center[2]])
+        }
+    }
+
+    /// Mirror across the plane through the origin orthogonal to `axis`, then rotate around `axis`.
+    pub fn rotoreflection(angle: Angle, axis: &[Scalar]) -> Self
+    {
+        // The reflection itself is a rotation by 180° (see Flector::reflection())
+        Flector { motor: DualQuaternion::rotor(angle + Angle::HALF, axis) }
+    }
+
+    /// The inverse improper motion.
+    pub fn inverse(&self) -> Self
+    {
+        // (M p->-p)^-1 = (p->-p) M^-1 = (p->-p) M^-1 (p->-p) (p->-p)
+        // and conjugating a motor by p -> -p negates its translation, i.e. its dual part.
+        Flector { motor: self.motor.inverse().iconj() }
+    }
+
+    /// Transform a 3D-vector as point.
+    pub fn transform_point(&self, point: &[Scalar]) -> [Scalar; 3]
+    {
+        self.motor.transform_point(&[-point[0], -point[1], -point[2]])
+    }
+
+    /// Transform a 3D-vector as direction, i.e. it won't be translated.
+    pub fn transform_direction(&self, direction: &[Scalar]) -> [Scalar; 3]
+    {
+        self.motor.transform_vector3(&[-direction[0], -direction[1], -direction[2]])
+    }
+}
+
+// Conjugating a motor by p -> -p negates its translation, i.e. (p->-p) M (p->-p) = M.iconj()
+
+auto_ops::impl_op_ex!(* |lhs: &Flector, rhs: &Flector| -> DualQuaternion { lhs.motor * rhs.motor.iconj() });
+auto_ops::impl_op_ex!(* |lhs: &Flector, rhs: &DualQuaternion| -> Flector { Flector { motor: lhs.motor * rhs.iconj() } });
+auto_ops::impl_op_ex!(* |lhs: &DualQuaternion, rhs: &Flector| -> Flector { Flector { motor: lhs * rhs.motor } });
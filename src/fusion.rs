@@ -0,0 +1,148 @@
+//! Attitude estimation from IMU samples, i.e. fusing gyroscope, accelerometer and (optionally) magnetometer readings.
+//!
+//! The estimated orientation rotates sensor coordinates into earth coordinates, with earth's z-axis pointing up.
+//! Gyroscope readings are in radians per second, accelerometer and magnetometer readings may have any unit,
+//! since only their directions are used. A resting accelerometer is expected to read +z (i.e. "up").
+
+pub use crate::quaternion::Quaternion;
+
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+/// Measured directions from the sensor frame together with the directions they should have in earth frame.
+/// Returns the sum of their cross products, i.e. the rotation (in sensor frame) which aligns the estimate
+/// with the measurements. Zero readings are ignored.
+fn correction(orientation: &Quaternion, accel: &[Scalar], mag: Option<&[Scalar]>) -> Vector3
+{
+    let accel = Vector3 { x: accel[0], y: accel[1], z: accel[2] };
+    if accel.norm_squared() < Scalar::EPSILON * Scalar::EPSILON {
+        return Vector3::ZERO
+    }
+
+    let to_sensor = orientation.conj();
+
+    // Estimated direction of gravity ("up") in the sensor frame
+    let up = Vector3::from(to_sensor.transform_vector(&[0.0, 0.0, 1.0]));
+    let mut error = accel.normalize().cross(&up);
+
+    if let Some(mag) = mag {
+        let mag = Vector3 { x: mag[0], y: mag[1], z: mag[2] };
+
+        if mag.norm_squared() > Scalar::EPSILON * Scalar::EPSILON {
+            // Earth's field has an unknown inclination, so only compare its horizontal direction:
+            // rotate the measurement into earth frame and move its horizontal part onto the x-axis (magnetic north)
+            let mag = mag.normalize();
+            let h = Vector3::from(orientation.transform_vector(&[mag.x, mag.y, mag.z]));
+            let north = [(h.x*h.x + h.y*h.y).sqrt(), 0.0, h.z];
+
+            let north = Vector3::from(to_sensor.transform_vector(&north));
+            error += mag.cross(&north);
+        }
+    }
+
+    error
+}
+
+/// Integrate the angular velocity `rate` (in sensor frame) over `dt` seconds.
+fn integrate(orientation: &Quaternion, rate: &Vector3, dt: Scalar) -> Quaternion
+{
+    // dq/dt = 0.5 * q * (0, ω)
+    let rate = Quaternion { w: 0.0, i: rate.x, j: rate.y, k: rate.z };
+
+    (*orientation + (0.5 * dt) * (orientation * rate)).normalized()
+}
+
+/// Madgwick's gradient descent filter.
+/// Higher `beta` trusts the accelerometer (and magnetometer) more, lower `beta` trusts the gyroscope more.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Madgwick
+{
+    pub orientation: Quaternion,
+    pub beta: Scalar,
+}
+
+impl Madgwick
+{
+    pub fn new(beta: Scalar) -> Self
+    {
+        Madgwick { orientation: Quaternion::ONE, beta }
+    }
+
+    /// Update with gyroscope and accelerometer readings taken `dt` seconds after the last update.
+    pub fn update_imu(&mut self, gyro: &[Scalar], accel: &[Scalar], dt: Scalar) -> Quaternion
+    {
+        self.update_with(gyro, accel, None, dt)
+    }
+
+    /// Update with gyroscope, accelerometer and magnetometer readings taken `dt` seconds after the last update.
+    pub fn update(&mut self, gyro: &[Scalar], accel: &[Scalar], mag: &[Scalar], dt: Scalar) -> Quaternion
+    {
+        self.update_with(gyro, accel, Some(mag), dt)
+    }
+
+    fn update_with(&mut self, gyro: &[Scalar], accel: &[Scalar], mag: Option<&[Scalar]>, dt: Scalar) -> Quaternion
+    {
+        // The gradient of Madgwick's objective function, restricted to rotations, is q * (0, -error)
+        // with the error from `correction()`. Like in the paper, it's normalized and scaled by beta.
+
+        let mut rate = Vector3 { x: gyro[0], y: gyro[1], z: gyro[2] };
+
+        let error = correction(&self.orientation, accel, mag);
+        if error.norm_squared() > Scalar::EPSILON * Scalar::EPSILON {
+            rate += 2.0 * self.beta * error.normalize();
+        }
+
+        self.orientation = integrate(&self.orientation, &rate, dt);
+        self.orientation
+    }
+}
+
+/// Mahony's complementary filter, a PI controller correcting the gyroscope's drift.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mahony
+{
+    pub orientation: Quaternion,
+    /// Proportional gain
+    pub kp: Scalar,
+    /// Integral gain, estimates the gyroscope's bias
+    pub ki: Scalar,
+    integral: [Scalar; 3],
+}
+
+impl Mahony
+{
+    pub fn new(kp: Scalar, ki: Scalar) -> Self
+    {
+        Mahony { orientation: Quaternion::ONE, kp, ki, integral: [0.0; 3] }
+    }
+
+    /// The current estimate of the gyroscope bias correction in radians per second.
+    pub fn bias_correction(&self) -> [Scalar; 3] { self.integral }
+
+    /// Update with gyroscope and accelerometer readings taken `dt` seconds after the last update.
+    pub fn update_imu(&mut self, gyro: &[Scalar], accel: &[Scalar], dt: Scalar) -> Quaternion
+    {
+        self.update_with(gyro, accel, None, dt)
+    }
+
+    /// Update with gyroscope, accelerometer and magnetometer readings taken `dt` seconds after the last update.
+    pub fn update(&mut self, gyro: &[Scalar], accel: &[Scalar], mag: &[Scalar], dt: Scalar) -> Quaternion
+    {
+        self.update_with(gyro, accel, Some(mag), dt)
+    }
+
+    fn update_with(&mut self, gyro: &[Scalar], accel: &[Scalar], mag: Option<&[Scalar]>, dt: Scalar) -> Quaternion
+    {
+        let error = correction(&self.orientation, accel, mag);
+
+        let integral = Vector3::from(self.integral) + (self.ki * dt) * error;
+        self.integral = integral.into();
+
+        let rate = Vector3 { x: gyro[0], y: gyro[1], z: gyro[2] } + self.kp * error + integral;
+
+        self.orientation = integrate(&self.orientation, &rate, dt);
+        self.orientation
+    }
+}
@@ -0,0 +1,330 @@
+//! Inverse kinematics for serial chains of ball joints.
+//!
+//! A chain is a base pose followed by joints. Every joint first moves by its fixed `offset`
+//! (e.g. the bone leading to it) and then rotates by its variable `rotation` around its own origin.
+//! The solvers only change the joint rotations, so the end effector reaches a target point.
+
+pub use crate::dual_quaternion::{DualQuaternion, Quaternion, Angle};
+
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Joint
+{
+    /// Fixed motor from the previous joint's frame (or the base) to this joint.
+    pub offset: DualQuaternion,
+    /// Variable rotation of this joint in its own frame.
+    pub rotation: Quaternion,
+}
+
+impl Joint
+{
+    /// A joint with the given offset and no rotation.
+    pub fn new(offset: DualQuaternion) -> Self
+    {
+        Joint { offset, rotation: Quaternion::ONE }
+    }
+
+    /// The motor of this joint in its parent's frame, i.e. offset followed by rotation.
+    pub fn motor(&self) -> DualQuaternion
+    {
+        let Quaternion { w, i, j, k } = self.rotation;
+
+        self.offset * DualQuaternion { w, i, j, k, ..DualQuaternion::ZERO }
+    }
+}
+
+/// One row of a Denavit–Hartenberg table. `theta` is the joint's rotation around its z-axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DhParams
+{
+    pub d:     Scalar,
+    pub theta: Angle,
+    pub a:     Scalar,
+    pub alpha: Angle,
+}
+
+/// How a Denavit–Hartenberg table is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DhConvention
+{
+    /// Each link is Rz(theta) Tz(d) Tx(a) Rx(alpha).
+    Standard,
+    /// Craig's convention, each link is Rx(alpha) Tx(a) Rz(theta) Tz(d).
+    Modified,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KinematicChain
+{
+    pub base:   DualQuaternion,
+    pub joints: Vec<Joint>,
+    /// Fixed motor from the last joint to the end effector.
+    pub tool:   DualQuaternion,
+}
+
+impl KinematicChain
+{
+    /// An empty chain, add joints with `.push()`.
+    pub fn new(base: DualQuaternion) -> Self
+    {
+        KinematicChain { base, joints: Vec::new(), tool: DualQuaternion::ONE }
+    }
+
+    /// Build a chain of revolute joints from a Denavit–Hartenberg table, with the base at the origin.
+    /// The joints rotate around their local z-axis. The solvers treat them as ball joints,
+    /// so use `IkSolver::with_limits()` to keep them rotating around z only.
+    pub fn from_dh(table: &[DhParams], convention: DhConvention) -> Self
+    {
+        // Rotations and translations along the same axis commute, so every link can be split into
+        // a fixed part before the joint's rotation Rz(theta) and a fixed part after it.
+
+        let z = |d: Scalar| DualQuaternion::translator(&[0.0, 0.0, d]);
+        let x = |a: Scalar, alpha: Angle| DualQuaternion::translator(&[a, 0.0, 0.0]) * DualQuaternion::rotor(alpha, &[1.0, 0.0, 0.0]);
+
+        let mut chain = KinematicChain::new(DualQuaternion::ONE);
+        let mut after_previous = DualQuaternion::ONE;
+
+        for row in table
+        {
+            let rotation = Quaternion::rotor(row.theta, &[0.0, 0.0, 1.0]);
+
+            let offset = match convention
+            {
+                // Rz Tz Tx Rx = Tz Rz (Tx Rx), the last part belongs to the next joint's offset
+                DhConvention::Standard => after_previous * z(row.d),
+                // Rx Tx Rz Tz = (Rx Tx Tz) Rz
+                DhConvention::Modified => x(0.0, row.alpha) * x(row.a, Angle::ZERO) * z(row.d),
+            };
+
+            if convention == DhConvention::Standard {
+                after_previous = x(row.a, row.alpha);
+            }
+
+            chain.push(Joint { offset, rotation });
+        }
+
+        chain.tool = after_previous;
+        chain
+    }
+
+    pub fn push(&mut self, joint: Joint) -> &mut Self
+    {
+        self.joints.push(joint);
+        self
+    }
+
+    /// World poses of all joints before their rotation is applied, i.e. their pivots.
+    pub fn joint_frames(&self) -> Vec<DualQuaternion>
+    {
+        let mut current = self.base;
+
+        self.joints.iter()
+            .map(|joint| {
+                let frame = current * joint.offset;
+                current = current * joint.motor();
+                frame
+            })
+            .collect()
+    }
+
+    /// World pose of the end effector (forward kinematics).
+    pub fn end_effector(&self) -> DualQuaternion
+    {
+        self.joints.iter().fold(self.base, |acc, joint| acc * joint.motor()) * self.tool
+    }
+
+    /// Rotate joint `index` by the world rotation `rotation` around its pivot.
+    fn rotate_joint(&mut self, index: usize, frame: &DualQuaternion, rotation: &Quaternion)
+    {
+        // The joint rotation acts in the joint's frame, so conjugate the world rotation with it
+        let local = frame.rotation();
+        let joint = &mut self.joints[index];
+
+        joint.rotation = (local.conj() * rotation * local * joint.rotation).normalized();
+    }
+}
+
+/// Outcome of a solver run. The chain is left in its best state even if it didn't converge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IkResult
+{
+    pub iterations: usize,
+    /// Distance between end effector and target.
+    pub error: Scalar,
+    pub converged: bool,
+}
+
+/// Shared settings for the CCD and FABRIK solvers.
+pub struct IkSolver
+{
+    pub max_iterations: usize,
+    /// The solvers stop once the end effector is closer to the target than this.
+    pub tolerance: Scalar,
+    limits: Option<Box<dyn Fn(usize, Quaternion) -> Quaternion>>,
+}
+
+impl Default for IkSolver
+{
+    fn default() -> Self
+    {
+        IkSolver { max_iterations: 32, tolerance: 1e-3, limits: None }
+    }
+}
+
+/// The shortest rotation turning direction `from` into direction `to`.
+fn shortest_arc(from: &Vector3, to: &Vector3) -> Quaternion
+{
+    // The half-way vector trick: 1 + a·b + a×b is the rotor of double the needed angle, normalized it's the right one
+
+    let (from, to) = (from.normalize(), to.normalize());
+    let axis = from.cross(&to);
+    let dot  = from.dot(&to);
+
+    // Opposite directions, rotate by 180° around any perpendicular axis
+    if 1.0 + dot < Scalar::EPSILON {
+        let helper = if from.x.abs() < 0.9 { Vector3::X } else { Vector3::Y };
+        let axis = from.cross(&helper).normalize();
+
+        return Quaternion { w: 0.0, i: axis.x, j: axis.y, k: axis.z }
+    }
+
+    Quaternion { w: 1.0 + dot, i: axis.x, j: axis.y, k: axis.z }.normalized()
+}
+
+impl IkSolver
+{
+    pub fn new(max_iterations: usize, tolerance: Scalar) -> Self
+    {
+        IkSolver { max_iterations, tolerance, limits: None }
+    }
+
+    /// Hook which gets the joint index and a proposed joint rotation and returns the allowed rotation.
+    /// It's called every time a solver changes a joint, e.g. to clamp angles or to project onto a hinge axis.
+    pub fn with_limits(mut self, limits: impl Fn(usize, Quaternion) -> Quaternion + 'static) -> Self
+    {
+        self.limits = Some(Box::new(limits));
+        self
+    }
+
+    fn limit(&self, chain: &mut KinematicChain, index: usize)
+    {
+        if let Some(limits) = &self.limits {
+            chain.joints[index].rotation = limits(index, chain.joints[index].rotation);
+        }
+    }
+
+    fn error(chain: &KinematicChain, target: &Vector3) -> Scalar
+    {
+        Vector3::from(chain.end_effector().translation()).distance_to(target)
+    }
+
+    fn result(&self, chain: &KinematicChain, target: &Vector3, iterations: usize) -> IkResult
+    {
+        let error = Self::error(chain, target);
+
+        IkResult { iterations, error, converged: error <= self.tolerance }
+    }
+
+    /// Cyclic coordinate descent: rotate every joint, from the end effector to the base,
+    /// so it points the end effector towards the target.
+    pub fn ccd(&self, chain: &mut KinematicChain, target: &[Scalar]) -> IkResult
+    {
+        let target = Vector3 { x: target[0], y: target[1], z: target[2] };
+
+        for iteration in 0..self.max_iterations
+        {
+            if Self::error(chain, &target) <= self.tolerance {
+                return self.result(chain, &target, iteration)
+            }
+
+            for index in (0..chain.joints.len()).rev()
+            {
+                let frame = chain.joint_frames()[index];
+                let pivot = Vector3::from(frame.translation());
+                let effector = Vector3::from(chain.end_effector().translation());
+
+                let (to_effector, to_target) = (effector - pivot, target - pivot);
+
+                if to_effector.norm_squared() < Scalar::EPSILON * Scalar::EPSILON || to_target.norm_squared() < Scalar::EPSILON * Scalar::EPSILON {
+                    continue
+                }
+
+                chain.rotate_joint(index, &frame, &shortest_arc(&to_effector, &to_target));
+                self.limit(chain, index);
+            }
+        }
+
+        self.result(chain, &target, self.max_iterations)
+    }
+
+    /// Forward and backward reaching inverse kinematics: move the joint positions alternately
+    /// from the target and from the base while keeping their distances, then turn the joints to match them.
+    pub fn fabrik(&self, chain: &mut KinematicChain, target: &[Scalar]) -> IkResult
+    {
+        let target = Vector3 { x: target[0], y: target[1], z: target[2] };
+
+        for iteration in 0..self.max_iterations
+        {
+            if Self::error(chain, &target) <= self.tolerance {
+                return self.result(chain, &target, iteration)
+            }
+
+            // Joint pivots followed by the end effector
+            let mut points: Vec<Vector3> = chain.joint_frames().iter()
+                .chain(core::iter::once(&chain.end_effector()))
+                .map(|frame| Vector3::from(frame.translation()))
+                .collect();
+
+            let lengths: Vec<Scalar> = points.windows(2).map(|p| p[0].distance_to(&p[1])).collect();
+            let root = points[0];
+            let last = points.len() - 1;
+
+            // Backward: pin the end effector to the target
+            points[last] = target;
+            for i in (0..last).rev()
+            {
+                let dir = points[i] - points[i + 1];
+                if dir.norm_squared() > Scalar::EPSILON * Scalar::EPSILON {
+                    points[i] = points[i + 1] + lengths[i] * dir.normalize();
+                }
+            }
+
+            // Forward: pin the first joint back to its pivot
+            points[0] = root;
+            for i in 0..last
+            {
+                let dir = points[i + 1] - points[i];
+                if dir.norm_squared() > Scalar::EPSILON * Scalar::EPSILON {
+                    points[i + 1] = points[i] + lengths[i] * dir.normalize();
+                }
+            }
+
+            // Turn the joints from the base outwards, so every bone points at its new position
+            for index in 0..chain.joints.len()
+            {
+                let frames = chain.joint_frames();
+                let pivot = Vector3::from(frames[index].translation());
+
+                let next = match frames.get(index + 1) {
+                    Some(frame) => frame.translation(),
+                    None => chain.end_effector().translation(),
+                };
+
+                let (current, desired) = (Vector3::from(next) - pivot, points[index + 1] - pivot);
+
+                if current.norm_squared() < Scalar::EPSILON * Scalar::EPSILON || desired.norm_squared() < Scalar::EPSILON * Scalar::EPSILON {
+                    continue
+                }
+
+                chain.rotate_joint(index, &frames[index], &shortest_arc(&current, &desired));
+                self.limit(chain, index);
+            }
+        }
+
+        self.result(chain, &target, self.max_iterations)
+    }
+}
@@ -1,10 +1,42 @@
 //! This crate contains types and functionality related to:
 //!
 //! * Complex numbers (`Complex`)
+//! * 2D rotations (`Rotor2D`)
+//! * 2D points and directions (`Vector2`)
+//! * 3D points and directions (`Vector3`)
+//! * Dual vectors for lines and screws (`DualVector3`)
 //! * Dual numbers (`DualNumber`)
+//! * Dual numbers with several dual parts for gradients (`DualN`)
+//! * Split-complex numbers (`SplitComplex`)
 //! * Quaternions (`Quaternion`)
 //! * Dual quaternions (`DualQuaternion`)
+//! * Octonions (`Octonion`)
+//! * Reflections and rotoreflections (`Flector`)
+//! * Dual complex numbers for rigid motions in the plane (`DualComplex`)
 //! * Angles (`Angle`)
+//! * Solid angles, e.g. of cones (`SolidAngle`)
+//! * Euler angles with their rotation order (`EulerAngles`)
+//! * Rotations given by axis and angle (`AxisAngle`)
+//! * Angular velocities (`AngularVelocity`)
+//! * Rigid transformations caching their matrix and inverse (`Transform`)
+//! * Similarity transformations, i.e. rigid transformations with uniform scale (`Similarity`)
+//! * Coordinate conventions like Y-up/Z-up and conversions between them (`Convention`)
+//! * Twists and wrenches from screw theory (`Twist`, `Wrench`)
+//! * Rigid body states integrated from forces and torques (`RigidState`)
+//! * Attitude estimation filters (`Madgwick`, `Mahony`)
+//! * Bivectors, i.e. oriented planes (`Bivector3`)
+//! * Matrices (`Matrix3`, `Matrix4`)
+//! * Homogeneous points (`Point4`)
+//! * Lines (`Line`)
+//! * Planes (`Plane`)
+//! * Rays (`Ray`)
+//! * Oriented bounding boxes (`Obb`)
+//! * Accumulation of relative poses with drift statistics (`PoseAccumulator`)
+//! * Catmull–Rom splines through poses, optionally by arc length (`MotorCatmullRom`, `ArcLengthSpline`)
+//! * Animation tracks of time-stamped poses (`PoseTrack`)
+//! * Inverse kinematics for chains of joints (`KinematicChain`, `IkSolver`)
+//! * Parallel bulk operations on (dual) quaternions (`parallel`, needs the `rayon` feature)
+//! * Construction macros (`vector2!`, `vector3!`, `quat!`, `dualquat!`)
 //!
 //! <div class="warning">
 //! This crate is still in development, but usable.
@@ -13,16 +45,69 @@
 //!
 //! # Cargo features
 //! * `angle_new_degrees` will make `Angle::new(angle)` use degrees as input (disabled by default)
-//! * `use_f64` will use f64 as scalar type for components instead of f32 (disabled by default)
+//! * `use_f64` will use f64 as scalar type (`Scalar`) for components instead of f32 (disabled by default)
+//! * `rayon` will add parallel versions of some bulk operations, see the `parallel` module (disabled by default)
+//! * `rand` will add random sampling of directions (disabled by default)
+//! * `num-complex` will add conversions between `Complex` and `num_complex::Complex` (disabled by default)
+//! * `std` links the standard library (enabled by default). Without it, the crate is `no_std` (but needs `alloc`)
+//!   and the `libm` feature has to be enabled for the math functions
+//! * `libm` will use `libm` for sqrt, sin, cos, exp, ln etc. when `std` is disabled (disabled by default)
 
+#![cfg_attr(not(feature = "std"), no_std)]
+// The test harness links std, whose inherent float methods make `util::Float` unused
+#![cfg_attr(all(test, not(feature = "std")), allow(unused_imports, dead_code))]
+
+#[cfg(all(not(feature = "std"), not(feature = "libm")))]
+compile_error!("without the `std` feature, the `libm` feature is needed for the math functions");
+
+extern crate alloc;
+
+// auto_ops expands to `::std::ops::*`, which are the same traits in core
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+
+pub use util::Scalar;
 
 pub mod angle;
+pub mod euler;
+pub mod axis_angle;
+pub mod angular_velocity;
+pub mod solid_angle;
 
 pub mod complex;
+pub mod rotor2d;
+pub mod vector2;
+pub mod vector3;
+pub mod dual_vector3;
 pub mod dual_numbers;
+pub mod dual_n;
+pub mod split_complex;
 
 pub mod quaternion;
 pub mod dual_quaternion;
+pub mod octonion;
+pub mod flector;
+pub mod dual_complex;
+pub mod transform;
+pub mod similarity;
+pub mod convention;
+
+pub mod bivector;
+pub mod screw;
+pub mod rigid_body;
+pub mod fusion;
+pub mod matrix;
+pub mod point4;
+pub mod line;
+pub mod plane;
+pub mod ray;
+pub mod obb;
+pub mod pose_graph;
+pub mod spline;
+pub mod animation;
+pub mod ik;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 
-mod util;
-mod vector3;
+pub mod util;
+mod macros;
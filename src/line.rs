@@ -0,0 +1,137 @@
+//! Lines in 3D space given by Plücker coordinates, i.e. a direction and a moment.
+//! DualQuaternions can also represent lines, but this type makes it explicit.
+
+use crate::dual_quaternion::DualQuaternion;
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+/// A line given by a normalized direction `dir` and its moment `moment = p X dir`
+/// for any point `p` on the line.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Line
+{
+    pub dir:    [Scalar; 3],
+    pub moment: [Scalar; 3],
+}
+
+impl From<Line> for DualQuaternion
+{
+    fn from(line: Line) -> Self
+    {
+        DualQuaternion {
+            w:  0.0,
+            i:  line.dir[0],
+            j:  line.dir[1],
+            k:  line.dir[2],
+            ie: line.moment[0],
+            je: line.moment[1],
+            ke: line.moment[2],
+            we: 0.0
+        }
+    }
+}
+
+/// Takes the vector parts of the DualQuaternion, the scalar parts are ignored.
+impl From<DualQuaternion> for Line
+{
+    fn from(dq: DualQuaternion) -> Self
+    {
+        Line { dir: [dq.i, dq.j, dq.k], moment: [dq.ie, dq.je, dq.ke] }
+    }
+}
+
+impl Line
+{
+    /// Create a line through the point `pos` in direction `dir`.
+    /// The direction will be normalized.
+    pub fn new(pos: &[Scalar], dir: &[Scalar]) -> Self
+    {
+        let pos = Vector3 { x: pos[0], y: pos[1], z: pos[2] };
+        let dir = Vector3 { x: dir[0], y: dir[1], z: dir[2] }.normalize();
+
+        Line { dir: dir.into(), moment: pos.cross(&dir).into() }
+    }
+
+    /// Create a line through two points (= join of two points).
+    /// The direction goes from `a` to `b`.
+    pub fn from_points(a: &[Scalar], b: &[Scalar]) -> Self
+    {
+        Self::new(a, &[b[0] - a[0], b[1] - a[1], b[2] - a[2]])
+    }
+
+    /// The direction of this line.
+    fn direction(&self) -> Vector3
+    {
+        Vector3 { x: self.dir[0], y: self.dir[1], z: self.dir[2] }
+    }
+
+    /// The moment of this line.
+    fn moment(&self) -> Vector3
+    {
+        Vector3 { x: self.moment[0], y: self.moment[1], z: self.moment[2] }
+    }
+
+    /// The point on this line nearest to the origin.
+    pub fn point(&self) -> [Scalar; 3]
+    {
+        self.direction().cross(&self.moment()).into()
+    }
+
+    /// The point on this line nearest to `point`.
+    pub fn closest_point(&self, point: &[Scalar]) -> [Scalar; 3]
+    {
+        let dir = self.direction();
+        let base = dir.cross(&self.moment());
+        let p = Vector3 { x: point[0], y: point[1], z: point[2] };
+
+        (base + dir * dir.dot(&(p - base))).into()
+    }
+
+    /// Distance between this line and `point`.
+    pub fn distance_to_point(&self, point: &[Scalar]) -> Scalar
+    {
+        // The moment of a parallel line through `point` is p X dir.
+        // The difference of both moments has the length of the distance (for normalized directions).
+
+        let p = Vector3 { x: point[0], y: point[1], z: point[2] };
+
+        (p.cross(&self.direction()) - self.moment()).norm()
+    }
+
+    /// The points on this and `other` line, which are closest to each other.
+    /// Returns `None` if the lines are parallel.
+    pub fn closest_points_between_lines(&self, other: &Line) -> Option<([Scalar; 3], [Scalar; 3])>
+    {
+        // https://en.wikipedia.org/wiki/Skew_lines#Nearest_points
+
+        let (d1, d2) = (self.direction(), other.direction());
+        let (p1, p2) = (d1.cross(&self.moment()), d2.cross(&other.moment()));
+
+        let n = d1.cross(&d2);
+        let nn = n.norm_squared();
+
+        if nn < Scalar::EPSILON {
+            return None
+        }
+
+        let d = p2 - p1;
+
+        let t1 = d.cross(&d2).dot(&n) / nn;
+        let t2 = d.cross(&d1).dot(&n) / nn;
+
+        Some(( (p1 + d1 * t1).into(), (p2 + d2 * t2).into() ))
+    }
+
+    /// Shortest distance between this and `other` line.
+    pub fn distance_to_line(&self, other: &Line) -> Scalar
+    {
+        match self.closest_points_between_lines(other)
+        {
+            Some((a, b)) => ((a[0]-b[0]).powi(2) + (a[1]-b[1]).powi(2) + (a[2]-b[2]).powi(2)).sqrt(),
+            None => self.distance_to_point(&other.point())
+        }
+    }
+}
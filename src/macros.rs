@@ -0,0 +1,62 @@
+//! Short construction macros, e.g. for tests and fixtures. They expand to struct literals, so they work in const contexts.
+
+/// Create a `Vector3` from its components, from a single value for all components,
+/// or from an array or tuple literal:
+/// `vector3!(1.0, 2.0, 3.0)`, `vector3!(0.5)`, `vector3!([1.0, 2.0, 3.0])`, `vector3!((1.0, 2.0, 3.0))`.
+///
+/// Arrays and tuples have to be literals. Any other single expression is taken as the value for all components,
+/// so e.g. `let a = [1.0, 2.0, 3.0]; vector3!(a)` fails with a type mismatch, use `Vector3::from(a)` for that.
+#[macro_export]
+macro_rules! vector3 {
+    ([$x:expr, $y:expr, $z:expr $(,)?]) => { $crate::vector3!($x, $y, $z) };
+    (($x:expr, $y:expr, $z:expr $(,)?)) => { $crate::vector3!($x, $y, $z) };
+    ($x:expr, $y:expr, $z:expr $(,)?) => {
+        $crate::vector3::Vector3 { x: $x, y: $y, z: $z }
+    };
+    ($splat:expr) => {{
+        // Only scalars can be splatted, arrays and tuples need to be literals (see above)
+        let splat: $crate::Scalar = $splat;
+        $crate::vector3::Vector3 { x: splat, y: splat, z: splat }
+    }};
+}
+
+/// Create a `Vector2` from its components, from a single value for both components,
+/// or from an array or tuple literal:
+/// `vector2!(1.0, 2.0)`, `vector2!(0.5)`, `vector2!([1.0, 2.0])`, `vector2!((1.0, 2.0))`.
+///
+/// Arrays and tuples have to be literals. Any other single expression is taken as the value for all components,
+/// so e.g. `let a = [1.0, 2.0]; vector2!(a)` fails with a type mismatch, use `Vector2::from(a)` for that.
+#[macro_export]
+macro_rules! vector2 {
+    ([$x:expr, $y:expr $(,)?]) => { $crate::vector2!($x, $y) };
+    (($x:expr, $y:expr $(,)?)) => { $crate::vector2!($x, $y) };
+    ($x:expr, $y:expr $(,)?) => {
+        $crate::vector2::Vector2 { x: $x, y: $y }
+    };
+    ($splat:expr) => {{
+        // Only scalars can be splatted, arrays and tuples need to be literals (see above)
+        let splat: $crate::Scalar = $splat;
+        $crate::vector2::Vector2 { x: splat, y: splat }
+    }};
+}
+
+/// Create a `Quaternion` from its components `w, i, j, k`, e.g. `quat!(1.0, 0.0, 0.0, 0.0)`.
+#[macro_export]
+macro_rules! quat {
+    ($w:expr, $i:expr, $j:expr, $k:expr $(,)?) => {
+        $crate::quaternion::Quaternion { w: $w, i: $i, j: $j, k: $k }
+    };
+}
+
+/// Create a `DualQuaternion` from its components in field order `w, i, j, k, ie, je, ke, we`,
+/// or from its real and dual part given as quaternions `[w, i, j, k]` and `[we, ie, je, ke]`:
+/// `dualquat!([1.0, 0.0, 0.0, 0.0], [0.0, 0.5, 0.0, 0.0])`.
+#[macro_export]
+macro_rules! dualquat {
+    ([$w:expr, $i:expr, $j:expr, $k:expr $(,)?], [$we:expr, $ie:expr, $je:expr, $ke:expr $(,)?]) => {
+        $crate::dualquat!($w, $i, $j, $k, $ie, $je, $ke, $we)
+    };
+    ($w:expr, $i:expr, $j:expr, $k:expr, $ie:expr, $je:expr, $ke:expr, $we:expr $(,)?) => {
+        $crate::dual_quaternion::DualQuaternion { w: $w, i: $i, j: $j, k: $k, ie: $ie, je: $je, ke: $ke, we: $we }
+    };
+}
@@ -0,0 +1,248 @@
+//! Small column-major matrices for interoperating with APIs which want matrices instead of (dual) quaternions.
+
+pub use crate::dual_quaternion::{DualQuaternion, Quaternion};
+
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+
+/// Column-major 3x3 matrix, i.e. `columns[column][row]`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Matrix3
+{
+    pub columns: [[Scalar; 3]; 3],
+}
+
+impl From<[[Scalar; 3]; 3]> for Matrix3
+{
+    fn from(columns: [[Scalar; 3]; 3]) -> Self { Matrix3 { columns } }
+}
+
+impl From<Matrix3> for [[Scalar; 3]; 3]
+{
+    fn from(matrix: Matrix3) -> Self { matrix.columns }
+}
+
+impl From<Quaternion> for Matrix3
+{
+    fn from(q: Quaternion) -> Self { Matrix3::from_quaternion(&q) }
+}
+
+impl Matrix3
+{
+    pub const ZERO:     Matrix3 = Matrix3 { columns: [[0.0; 3]; 3] };
+    pub const IDENTITY: Matrix3 = Matrix3 { columns: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]] };
+
+    /// Create a matrix from its columns.
+    pub fn new(x: [Scalar; 3], y: [Scalar; 3], z: [Scalar; 3]) -> Self
+    {
+        Matrix3 { columns: [x, y, z] }
+    }
+
+    /// Rotation matrix of a rotor. Only works for normalized quaternions.
+    pub fn from_quaternion(q: &Quaternion) -> Self
+    {
+        Matrix3 {
+            columns: [
+                q.transform_vector(&[1.0, 0.0, 0.0]),
+                q.transform_vector(&[0.0, 1.0, 0.0]),
+                q.transform_vector(&[0.0, 0.0, 1.0]),
+            ]
+        }
+    }
+
+    /// Rotor of a rotation matrix. Only works for rotation matrices.
+    pub fn to_quaternion(&self) -> Quaternion
+    {
+        let [x, y, z] = self.columns.map(|c| Vector3 { x: c[0], y: c[1], z: c[2] });
+
+        Quaternion::from_rotation_columns(x, y, z)
+    }
+
+    /// Get the entry in `row` and `column`.
+    pub fn get(&self, row: usize, column: usize) -> Scalar
+    {
+        self.columns[column][row]
+    }
+
+    pub fn transpose(&self) -> Self
+    {
+        Matrix3 { columns: core::array::from_fn(|col| core::array::from_fn(|row| self.columns[row][col])) }
+    }
+
+    pub fn determinant(&self) -> Scalar
+    {
+        let [x, y, z] = self.columns.map(|c| Vector3 { x: c[0], y: c[1], z: c[2] });
+
+        x.dot(&y.cross(&z))
+    }
+
+    /// Inverse of this matrix. Returns `None` if the matrix isn't invertible.
+    pub fn inverse(&self) -> Option<Self>
+    {
+        // The rows of the inverse are the cross products of the columns divided by the determinant
+
+        let [x, y, z] = self.columns.map(|c| Vector3 { x: c[0], y: c[1], z: c[2] });
+        let det = x.dot(&y.cross(&z));
+
+        // Compare against the volume spanned by the column lengths, so uniformly scaled matrices behave the same
+        let scale = x.norm() * y.norm() * z.norm();
+
+        if !det.is_finite() || det.abs() <= Scalar::EPSILON * scale {
+            return None
+        }
+
+        let rows = Matrix3 {
+            columns: [
+                (y.cross(&z) * (1.0 / det)).into(),
+                (z.cross(&x) * (1.0 / det)).into(),
+                (x.cross(&y) * (1.0 / det)).into(),
+            ]
+        };
+
+        Some(rows.transpose())
+    }
+
+    /// Multiply a column vector by this matrix.
+    pub fn transform(&self, vector: &[Scalar]) -> [Scalar; 3]
+    {
+        let m = &self.columns;
+
+        core::array::from_fn(|row| m[0][row] * vector[0] + m[1][row] * vector[1] + m[2][row] * vector[2])
+    }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &Matrix3, rhs: &Matrix3| -> Matrix3 {
+    Matrix3 { columns: rhs.columns.map(|column| lhs.transform(&column)) }
+});
+auto_ops::impl_op_ex!(* |lhs: &Matrix3, rhs: &[Scalar; 3]| -> [Scalar; 3] { lhs.transform(rhs) });
+auto_ops::impl_op_ex_commutative!(* |lhs: &Matrix3, rhs: &Scalar| -> Matrix3 {
+    Matrix3 { columns: lhs.columns.map(|column| column.map(|x| x * rhs)) }
+});
+auto_ops::impl_op_ex!(+ |lhs: &Matrix3, rhs: &Matrix3| -> Matrix3 {
+    Matrix3 { columns: core::array::from_fn(|col| core::array::from_fn(|row| lhs.columns[col][row] + rhs.columns[col][row])) }
+});
+auto_ops::impl_op_ex!(- |lhs: &Matrix3, rhs: &Matrix3| -> Matrix3 {
+    Matrix3 { columns: core::array::from_fn(|col| core::array::from_fn(|row| lhs.columns[col][row] - rhs.columns[col][row])) }
+});
+
+/// Column-major 4x4 matrix, i.e. `columns[column][row]`, mostly used for homogeneous transformations.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Matrix4
+{
+    pub columns: [[Scalar; 4]; 4],
+}
+
+impl From<[[Scalar; 4]; 4]> for Matrix4
+{
+    fn from(columns: [[Scalar; 4]; 4]) -> Self { Matrix4 { columns } }
+}
+
+impl From<Matrix4> for [[Scalar; 4]; 4]
+{
+    fn from(matrix: Matrix4) -> Self { matrix.columns }
+}
+
+impl From<DualQuaternion> for Matrix4
+{
+    fn from(dq: DualQuaternion) -> Self { Matrix4 { columns: dq.to_matrix4() } }
+}
+
+impl Matrix4
+{
+    pub const ZERO:     Matrix4 = Matrix4 { columns: [[0.0; 4]; 4] };
+    pub const IDENTITY: Matrix4 = Matrix4 {
+        columns: [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]]
+    };
+
+    /// Matrix of a rigid transformation, see `DualQuaternion::to_matrix4()`.
+    pub fn from_dual_quaternion(dq: &DualQuaternion) -> Self
+    {
+        Matrix4 { columns: dq.to_matrix4() }
+    }
+
+    /// Motor of a rigid transformation. Only works for rigid transformation matrices.
+    /// Use `DualQuaternion::from_matrix4_renormalized()` if the matrix may be slightly off.
+    pub fn to_dual_quaternion(&self) -> DualQuaternion
+    {
+        let rotation = self.rotation().to_quaternion();
+        let t = &self.columns[3];
+
+        let rotation = DualQuaternion {
+            w: rotation.w, i: rotation.i, j: rotation.j, k: rotation.k,
+            ..DualQuaternion::ZERO
+        };
+
+        DualQuaternion::translator(&t[..3]) * rotation
+    }
+
+    /// The upper left 3x3 part, i.e. the rotation for rigid transformations.
+    pub fn rotation(&self) -> Matrix3
+    {
+        Matrix3 { columns: core::array::from_fn(|col| core::array::from_fn(|row| self.columns[col][row])) }
+    }
+
+    /// Get the entry in `row` and `column`.
+    pub fn get(&self, row: usize, column: usize) -> Scalar
+    {
+        self.columns[column][row]
+    }
+
+    pub fn transpose(&self) -> Self
+    {
+        Matrix4 { columns: core::array::from_fn(|col| core::array::from_fn(|row| self.columns[row][col])) }
+    }
+
+    /// Inverse of a rigid transformation matrix, i.e. (R t) -> (R^T -R^T t).
+    /// Produces wrong results for other matrices.
+    pub fn rigid_inverse(&self) -> Self
+    {
+        let r = self.rotation().transpose();
+        let t = r.transform(&self.columns[3][..3]);
+
+        let c = r.columns;
+
+        Matrix4 {
+            columns: [
+                [c[0][0], c[0][1], c[0][2], 0.0],
+                [c[1][0], c[1][1], c[1][2], 0.0],
+                [c[2][0], c[2][1], c[2][2], 0.0],
+                [-t[0],   -t[1],   -t[2],   1.0],
+            ]
+        }
+    }
+
+    /// Multiply a homogeneous column vector by this matrix.
+    pub fn transform(&self, vector: &[Scalar; 4]) -> [Scalar; 4]
+    {
+        let m = &self.columns;
+
+        core::array::from_fn(|row| (0..4).map(|col| m[col][row] * vector[col]).sum())
+    }
+
+    /// Transform a 3D-vector as point, i.e. with w = 1.
+    /// No perspective divide is done.
+    pub fn transform_point(&self, point: &[Scalar]) -> [Scalar; 3]
+    {
+        let p = self.transform(&[point[0], point[1], point[2], 1.0]);
+
+        [p[0], p[1], p[2]]
+    }
+
+    /// Transform a 3D-vector as direction, i.e. with w = 0, so it won't be translated.
+    pub fn transform_direction(&self, direction: &[Scalar]) -> [Scalar; 3]
+    {
+        let d = self.transform(&[direction[0], direction[1], direction[2], 0.0]);
+
+        [d[0], d[1], d[2]]
+    }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &Matrix4, rhs: &Matrix4| -> Matrix4 {
+    Matrix4 { columns: rhs.columns.map(|column| lhs.transform(&column)) }
+});
+auto_ops::impl_op_ex!(* |lhs: &Matrix4, rhs: &[Scalar; 4]| -> [Scalar; 4] { lhs.transform(rhs) });
+auto_ops::impl_op_ex_commutative!(* |lhs: &Matrix4, rhs: &Scalar| -> Matrix4 {
+    Matrix4 { columns: lhs.columns.map(|column| column.map(|x| x * rhs)) }
+});
@@ -0,0 +1,102 @@
+//! Oriented bounding boxes, e.g. as collision proxies of rigidly moving objects.
+
+pub use crate::dual_quaternion::{DualQuaternion, Quaternion};
+
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+
+/// Box around `center`, rotated by `orientation`, reaching `half_extents` along its local x-, y- and z-axis.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Obb
+{
+    pub center:       [Scalar; 3],
+    pub half_extents: [Scalar; 3],
+    /// Normalized rotor
+    pub orientation:  Quaternion,
+}
+
+impl Obb
+{
+    pub fn new(center: &[Scalar], half_extents: &[Scalar], orientation: Quaternion) -> Self
+    {
+        Obb {
+            center:       [center[0], center[1], center[2]],
+            half_extents: [half_extents[0], half_extents[1], half_extents[2]],
+            orientation,
+        }
+    }
+
+    /// The box's local axes in world space.
+    fn axes(&self) -> [Vector3; 3]
+    {
+        [
+            Vector3::from(self.orientation.transform_vector(&[1.0, 0.0, 0.0])),
+            Vector3::from(self.orientation.transform_vector(&[0.0, 1.0, 0.0])),
+            Vector3::from(self.orientation.transform_vector(&[0.0, 0.0, 1.0])),
+        ]
+    }
+
+    /// The same box, moved by `motor`.
+    /// Only works on normalized dual quaternions.
+    pub fn transform(&self, motor: &DualQuaternion) -> Self
+    {
+        Obb {
+            center:       motor.transform_point(&self.center),
+            half_extents: self.half_extents,
+            orientation:  (motor.rotation() * self.orientation).normalized(),
+        }
+    }
+
+    /// The eight corners of this box.
+    pub fn corners(&self) -> [[Scalar; 3]; 8]
+    {
+        let [x, y, z] = self.axes();
+        let [hx, hy, hz] = self.half_extents;
+        let center = Vector3::from(self.center);
+
+        core::array::from_fn(|i| {
+            let sign = |bit: usize| if i & (1 << bit) == 0 { -1.0 } else { 1.0 };
+
+            (center + (sign(0) * hx) * x + (sign(1) * hy) * y + (sign(2) * hz) * z).into()
+        })
+    }
+
+    /// Whether `point` lies inside this box (or on its surface).
+    pub fn contains_point(&self, point: &[Scalar]) -> bool
+    {
+        let d = Vector3 { x: point[0], y: point[1], z: point[2] } - Vector3::from(self.center);
+
+        self.axes().iter().zip(self.half_extents).all(|(axis, h)| axis.dot(&d).abs() <= h)
+    }
+
+    /// Half the length of this box's shadow on `axis`.
+    fn projected_radius(&self, axes: &[Vector3; 3], axis: &Vector3) -> Scalar
+    {
+        axes.iter().zip(self.half_extents).map(|(a, h)| h * a.dot(axis).abs()).sum()
+    }
+
+    /// Whether this and `other` box intersect (touching counts as intersecting).
+    pub fn overlaps(&self, other: &Obb) -> bool
+    {
+        // Separating axis theorem: two convex shapes are disjoint if their shadows on some axis are.
+        // For boxes, it suffices to test the 3 + 3 face normals and the 9 cross products of their edges.
+
+        let (a, b) = (self.axes(), other.axes());
+        let d = Vector3::from(other.center) - Vector3::from(self.center);
+
+        let separates = |axis: Vector3| {
+            // Cross products of (nearly) parallel edges are (nearly) zero and can't separate anything
+            if axis.norm_squared() < Scalar::EPSILON {
+                return false
+            }
+
+            d.dot(&axis).abs() > self.projected_radius(&a, &axis) + other.projected_radius(&b, &axis)
+        };
+
+        let faces = a.iter().chain(b.iter()).any(|axis| separates(*axis));
+        let edges = a.iter().any(|x| b.iter().any(|y| separates(x.cross(y))));
+
+        !(faces || edges)
+    }
+}
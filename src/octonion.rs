@@ -0,0 +1,112 @@
+//! Octonions are the next step after quaternions in the Cayley–Dickson construction.
+//! Their multiplication is neither commutative nor associative, so they are mostly useful for experimentation.
+
+pub use crate::quaternion::Quaternion;
+
+use crate::util::{Scalar, write_terms};
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
+    derive_more::Neg
+)]
+pub struct Octonion
+{
+    pub e0: Scalar,
+    pub e1: Scalar,
+    pub e2: Scalar,
+    pub e3: Scalar,
+    pub e4: Scalar,
+    pub e5: Scalar,
+    pub e6: Scalar,
+    pub e7: Scalar,
+}
+
+impl core::fmt::Display for Octonion
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_terms(f, &[
+            (self.e0, ""), (self.e1, "e1"), (self.e2, "e2"), (self.e3, "e3"),
+            (self.e4, "e4"), (self.e5, "e5"), (self.e6, "e6"), (self.e7, "e7"),
+        ])
+    }
+}
+
+/// The octonion a + b*e4, where a and b are quaternions (Cayley–Dickson construction)
+impl From<(Quaternion, Quaternion)> for Octonion
+{
+    fn from((a, b): (Quaternion, Quaternion)) -> Self { Octonion::from_quaternions(&a, &b) }
+}
+
+impl Octonion
+{
+    pub const ZERO: Self = Self { e0: 0.0, e1: 0.0, e2: 0.0, e3: 0.0, e4: 0.0, e5: 0.0, e6: 0.0, e7: 0.0 };
+    pub const ONE:  Self = Self { e0: 1.0, ..Self::ZERO };
+
+    /// The octonion a + b*e4, where a and b are quaternions (Cayley–Dickson construction)
+    pub fn from_quaternions(a: &Quaternion, b: &Quaternion) -> Self
+    {
+        Octonion {
+            e0: a.w, e1: a.i, e2: a.j, e3: a.k,
+            e4: b.w, e5: b.i, e6: b.j, e7: b.k,
+        }
+    }
+
+    /// Split this octonion into the quaternions a and b, such that it is a + b*e4
+    pub fn to_quaternions(&self) -> (Quaternion, Quaternion)
+    {
+        (
+            Quaternion { w: self.e0, i: self.e1, j: self.e2, k: self.e3 },
+            Quaternion { w: self.e4, i: self.e5, j: self.e6, k: self.e7 },
+        )
+    }
+
+    /// Negate all imaginary parts
+    pub fn conj(&self) -> Self
+    {
+        Octonion {
+            e0:  self.e0,
+            e1: -self.e1, e2: -self.e2, e3: -self.e3,
+            e4: -self.e4, e5: -self.e5, e6: -self.e6, e7: -self.e7,
+        }
+    }
+
+    pub fn norm(&self) -> Scalar
+    {
+        (self.e0*self.e0 + self.e1*self.e1 + self.e2*self.e2 + self.e3*self.e3 +
+         self.e4*self.e4 + self.e5*self.e5 + self.e6*self.e6 + self.e7*self.e7).sqrt()
+    }
+
+    pub fn normalized(&self) -> Self { *self * (1.0 / self.norm()) }
+
+    /// Multiplicative inverse.
+    /// May produce invalid numbers if this octonion is 0.0
+    pub fn inverse(&self) -> Self { self.conj() * (1.0 / self.norm().powi(2)) }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &Octonion, rhs: &Octonion| -> Octonion {
+    // https://en.wikipedia.org/wiki/Cayley%E2%80%93Dickson_construction
+    // (a,b)(c,d) = (ac - d*b, da + bc*)
+
+    let (a, b) = lhs.to_quaternions();
+    let (c, d) = rhs.to_quaternions();
+
+    Octonion::from_quaternions(
+        &(a * c - d.conj() * b),
+        &(d * a + b * c.conj())
+    )
+});
+auto_ops::impl_op_ex_commutative!(* |lhs: &Octonion, rhs: &Scalar| -> Octonion {
+    Octonion {
+        e0: lhs.e0 * rhs, e1: lhs.e1 * rhs, e2: lhs.e2 * rhs, e3: lhs.e3 * rhs,
+        e4: lhs.e4 * rhs, e5: lhs.e5 * rhs, e6: lhs.e6 * rhs, e7: lhs.e7 * rhs,
+    }
+});
+auto_ops::impl_op_ex!(*= |lhs: &mut Octonion, rhs: &Scalar| { *lhs = *lhs * rhs });
+
+// Octonions are alternative, so (a*b)*b^-1 = a and dividing like this works
+auto_ops::impl_op_ex!(/ |lhs: &Octonion, rhs: &Octonion| -> Octonion { lhs * rhs.inverse() });
+auto_ops::impl_op_ex!(/ |lhs: &Octonion, rhs: &Scalar| -> Octonion { lhs * (1.0 / rhs) });
@@ -0,0 +1,104 @@
+//! Parallel versions of bulk operations on (dual) quaternions, e.g. for large pose buffers (feature `rayon`).
+//!
+//! The slices are split into chunks which are processed on rayon's thread pool,
+//! so this is only worth it for large amounts of data.
+
+pub use crate::dual_quaternion::{DualQuaternion, Quaternion};
+
+use crate::util::Scalar;
+use rayon::prelude::*;
+
+/// Number of elements processed at once by one task
+const CHUNK: usize = 4096;
+
+/// Transform many points by `motor`, see `DualQuaternion::transform_point()`.
+pub fn transform_points(motor: &DualQuaternion, points: &mut [[Scalar; 3]])
+{
+    motor.par_transform_points_in_place(points);
+}
+
+/// Rotate many vectors by `rotation`, see `Quaternion::transform_vector()`.
+pub fn rotate_vectors(rotation: &Quaternion, vectors: &mut [[Scalar; 3]])
+{
+    vectors.par_chunks_mut(CHUNK).for_each(|chunk| {
+        for vector in chunk.iter_mut()
+        {
+            *vector = rotation.transform_vector(vector);
+        }
+    });
+}
+
+/// Transform every pose by `motor` from the left, i.e. `pose = motor * pose`, e.g. to move a whole pose buffer.
+pub fn transform_poses(motor: &DualQuaternion, poses: &mut [DualQuaternion])
+{
+    poses.par_chunks_mut(CHUNK).for_each(|chunk| {
+        for pose in chunk.iter_mut()
+        {
+            *pose = motor * *pose;
+        }
+    });
+}
+
+/// Normalize all quaternions in place.
+pub fn normalize_quaternions(quaternions: &mut [Quaternion])
+{
+    quaternions.par_chunks_mut(CHUNK).for_each(|chunk| {
+        for q in chunk.iter_mut()
+        {
+            *q = q.normalized();
+        }
+    });
+}
+
+/// Normalize all dual quaternions in place, see `DualQuaternion::normalized()`.
+pub fn normalize_dual_quaternions(motors: &mut [DualQuaternion])
+{
+    motors.par_chunks_mut(CHUNK).for_each(|chunk| {
+        for motor in chunk.iter_mut()
+        {
+            *motor = motor.normalized();
+        }
+    });
+}
+
+/// Align all motors with the hemisphere of the first one, see `DualQuaternion::align_hemispheres()`.
+pub fn align_hemispheres(motors: &mut [DualQuaternion])
+{
+    let Some(&reference) = motors.first() else { return };
+
+    motors.par_chunks_mut(CHUNK).for_each(|chunk| {
+        for motor in chunk.iter_mut()
+        {
+            *motor = motor.aligned_with(&reference);
+        }
+    });
+}
+
+/// Spherically interpolate `a` and `b` element-wise into `out`, see `Quaternion::slerp()`.
+/// Panics if the slices have different lengths.
+pub fn slerp(a: &[Quaternion], b: &[Quaternion], alpha: Scalar, out: &mut [Quaternion])
+{
+    assert!(a.len() == b.len() && a.len() == out.len(), "slices must have the same length");
+
+    out.par_chunks_mut(CHUNK).zip(a.par_chunks(CHUNK)).zip(b.par_chunks(CHUNK)).for_each(|((out, a), b)| {
+        for ((out, a), b) in out.iter_mut().zip(a).zip(b)
+        {
+            *out = a.slerp(*b, alpha);
+        }
+    });
+}
+
+/// Screw-interpolate `a` and `b` element-wise into `out`, e.g. to blend two pose buffers, see `DualQuaternion::sclerp()`.
+/// `b` is aligned with the hemisphere of `a` first, so the interpolation takes the short way.
+/// Only works on normalized dual quaternions. Panics if the slices have different lengths.
+pub fn sclerp(a: &[DualQuaternion], b: &[DualQuaternion], alpha: Scalar, out: &mut [DualQuaternion])
+{
+    assert!(a.len() == b.len() && a.len() == out.len(), "slices must have the same length");
+
+    out.par_chunks_mut(CHUNK).zip(a.par_chunks(CHUNK)).zip(b.par_chunks(CHUNK)).for_each(|((out, a), b)| {
+        for ((out, a), b) in out.iter_mut().zip(a).zip(b)
+        {
+            *out = a.sclerp(&b.aligned_with(a), alpha);
+        }
+    });
+}
@@ -0,0 +1,148 @@
+//! Planes in 3D space given by a normal and an offset from the origin.
+
+use crate::line::Line;
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+
+/// A plane containing all points `p` with `normal · p = offset`.
+/// The normal is normalized, so `offset` is the signed distance of the plane from the origin.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Plane
+{
+    pub normal: [Scalar; 3],
+    pub offset: Scalar,
+}
+
+/// Cross product of `a` and `b`, or `None` if they are parallel (up to rounding errors) or zero.
+/// Compared to their lengths, so it doesn't depend on the units.
+fn normal_of(a: Vector3, b: Vector3) -> Option<Vector3>
+{
+    let normal = a.cross(&b);
+
+    if normal.norm_squared() <= Scalar::EPSILON * Scalar::EPSILON * a.norm_squared() * b.norm_squared() {
+        return None
+    }
+
+    Some(normal)
+}
+
+impl Plane
+{
+    /// Create a plane through `point` with the normal `normal`.
+    /// The normal will be normalized, so a zero normal gives a NaN plane.
+    pub fn new(point: &[Scalar], normal: &[Scalar]) -> Self
+    {
+        let point  = Vector3 { x: point[0],  y: point[1],  z: point[2]  };
+        let normal = Vector3 { x: normal[0], y: normal[1], z: normal[2] }.normalize();
+
+        Plane { normal: normal.into(), offset: normal.dot(&point) }
+    }
+
+    /// Create a plane through three points.
+    /// The normal points to the side from which `a`, `b`, `c` appear counter-clockwise.
+    /// Returns `None` if the points are collinear.
+    pub fn from_three_points(a: &[Scalar], b: &[Scalar], c: &[Scalar]) -> Option<Self>
+    {
+        let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+
+        let ab = Vector3 { x: ab[0], y: ab[1], z: ab[2] };
+        let ac = Vector3 { x: ac[0], y: ac[1], z: ac[2] };
+
+        let normal = normal_of(ab, ac)?;
+
+        Some(Self::new(a, &<[Scalar; 3]>::from(normal)))
+    }
+
+    /// Create the plane containing `line` and `point` (= join of a line and a point).
+    /// Returns `None` if the point lies on the line.
+    pub fn from_line_and_point(line: &Line, point: &[Scalar]) -> Option<Self>
+    {
+        let on_line = line.point();
+
+        let a = Vector3 { x: line.dir[0], y: line.dir[1], z: line.dir[2] };
+        let b = Vector3 { x: point[0] - on_line[0], y: point[1] - on_line[1], z: point[2] - on_line[2] };
+
+        let normal = normal_of(a, b)?;
+
+        Some(Self::new(point, &<[Scalar; 3]>::from(normal)))
+    }
+
+    fn normal(&self) -> Vector3
+    {
+        Vector3 { x: self.normal[0], y: self.normal[1], z: self.normal[2] }
+    }
+
+    /// Signed distance of `point` to this plane.
+    /// Positive if the point lies on the side the normal points to.
+    pub fn signed_distance(&self, point: &[Scalar]) -> Scalar
+    {
+        let point = Vector3 { x: point[0], y: point[1], z: point[2] };
+
+        self.normal().dot(&point) - self.offset
+    }
+
+    /// The point on this plane nearest to `point`.
+    pub fn project_point(&self, point: &[Scalar]) -> [Scalar; 3]
+    {
+        let d = self.signed_distance(point);
+        let p = Vector3 { x: point[0], y: point[1], z: point[2] };
+
+        (p - d * self.normal()).into()
+    }
+
+    /// Intersection point of `line` and this plane (= meet of a line and a plane).
+    /// Returns `None` if the line is parallel to the plane.
+    pub fn intersect_line(&self, line: &Line) -> Option<[Scalar; 3]>
+    {
+        let dir = Vector3 { x: line.dir[0], y: line.dir[1], z: line.dir[2] };
+        let denom = self.normal().dot(&dir);
+
+        if denom.abs() < Scalar::EPSILON {
+            return None
+        }
+
+        let p = line.point();
+        let t = -self.signed_distance(&p) / denom;
+
+        Some([p[0] + t * dir.x, p[1] + t * dir.y, p[2] + t * dir.z])
+    }
+
+    /// Intersection line of this and `other` plane (= meet of two planes).
+    /// Returns `None` if the planes are parallel.
+    pub fn intersect_plane(&self, other: &Plane) -> Option<Line>
+    {
+        // https://en.wikipedia.org/wiki/Plane%E2%80%93plane_intersection
+
+        let (n1, n2) = (self.normal(), other.normal());
+        let dir = n1.cross(&n2);
+        let dd = dir.norm_squared();
+
+        if dd < Scalar::EPSILON {
+            return None
+        }
+
+        // Point on both planes nearest to the origin
+        let point = (self.offset * n2.cross(&dir) + other.offset * dir.cross(&n1)) * (1.0 / dd);
+
+        Some(Line::new(&<[Scalar; 3]>::from(point), &<[Scalar; 3]>::from(dir)))
+    }
+
+    /// Mirror `point` across this plane.
+    pub fn reflect_point(&self, point: &[Scalar]) -> [Scalar; 3]
+    {
+        let d = self.signed_distance(point);
+        let p = Vector3 { x: point[0], y: point[1], z: point[2] };
+
+        (p - 2.0 * d * self.normal()).into()
+    }
+
+    /// Mirror a direction across this plane, i.e. the plane's offset is ignored.
+    pub fn reflect_direction(&self, direction: &[Scalar]) -> [Scalar; 3]
+    {
+        let v = Vector3 { x: direction[0], y: direction[1], z: direction[2] };
+
+        v.reflect(&self.normal()).into()
+    }
+}
@@ -0,0 +1,99 @@
+//! Points in homogeneous (projective) coordinates, e.g. for clip space.
+
+pub use crate::matrix::{Matrix4, DualQuaternion};
+
+use crate::util::Scalar;
+
+/// The point (x/w, y/w, z/w). If w is 0.0, it's a point at infinity, i.e. a direction.
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
+    derive_more::Neg
+)]
+pub struct Point4
+{
+    pub x: Scalar,
+    pub y: Scalar,
+    pub z: Scalar,
+    pub w: Scalar,
+}
+
+impl From<[Scalar; 4]> for Point4
+{
+    fn from([x, y, z, w]: [Scalar; 4]) -> Self { Point4 { x, y, z, w } }
+}
+
+impl From<Point4> for [Scalar; 4]
+{
+    fn from(p: Point4) -> Self { [p.x, p.y, p.z, p.w] }
+}
+
+/// A 3D-point with w = 1.0
+impl From<[Scalar; 3]> for Point4
+{
+    fn from([x, y, z]: [Scalar; 3]) -> Self { Point4 { x, y, z, w: 1.0 } }
+}
+
+impl Point4
+{
+    pub fn new(x: Scalar, y: Scalar, z: Scalar, w: Scalar) -> Self
+    {
+        Point4 { x, y, z, w }
+    }
+
+    /// Homogeneous coordinates of a 3D-point, i.e. with w = 1.0
+    pub fn point(point: &[Scalar]) -> Self
+    {
+        Point4 { x: point[0], y: point[1], z: point[2], w: 1.0 }
+    }
+
+    /// Homogeneous coordinates of a direction, i.e. with w = 0.0, so it won't be translated.
+    pub fn direction(direction: &[Scalar]) -> Self
+    {
+        Point4 { x: direction[0], y: direction[1], z: direction[2], w: 0.0 }
+    }
+
+    pub fn is_at_infinity(&self) -> bool
+    {
+        self.w.abs() < Scalar::EPSILON
+    }
+
+    /// Divide by w, e.g. to get from clip space to normalized device coordinates.
+    /// Returns `None` for points at infinity.
+    pub fn perspective_divide(&self) -> Option<[Scalar; 3]>
+    {
+        if self.is_at_infinity() {
+            return None
+        }
+
+        Some([self.x / self.w, self.y / self.w, self.z / self.w])
+    }
+
+    /// The first three coordinates, ignoring w.
+    pub fn xyz(&self) -> [Scalar; 3]
+    {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn transform(&self, matrix: &Matrix4) -> Self
+    {
+        matrix.transform(&(*self).into()).into()
+    }
+
+    /// Apply a rigid transformation. Only the xyz part gets moved, scaled by w like a matrix would.
+    /// Only works on normalized dual quaternions.
+    pub fn transform_motor(&self, motor: &DualQuaternion) -> Self
+    {
+        // Rotation acts on xyz and the translation is weighted by w, i.e. R*xyz + w*t
+        let [x, y, z] = motor.transform_vector3(&self.xyz());
+        let t = motor.translation();
+
+        Point4 { x: x + self.w * t[0], y: y + self.w * t[1], z: z + self.w * t[2], w: self.w }
+    }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &Matrix4, rhs: &Point4| -> Point4 { rhs.transform(lhs) });
+auto_ops::impl_op_ex_commutative!(* |lhs: &Point4, rhs: &Scalar| -> Point4 {
+    Point4 { x: lhs.x * rhs, y: lhs.y * rhs, z: lhs.z * rhs, w: lhs.w * rhs }
+});
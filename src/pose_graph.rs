@@ -0,0 +1,108 @@
+//! Accumulating relative poses (e.g. from odometry) and measuring how far they drift from a ground truth.
+//!
+//! All poses map local coordinates to world coordinates, relative poses are given in the frame of the previous pose.
+//! So the next pose is always `previous * relative`, see `DualQuaternion::relative()`.
+
+pub use crate::dual_quaternion::{DualQuaternion, Angle};
+
+use crate::util::Scalar;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Difference between an estimated and a true pose.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drift
+{
+    /// Distance between the estimated and the true position.
+    pub translation: Scalar,
+    /// Angle of the rotation between the estimated and the true orientation.
+    pub rotation: Angle,
+    /// Translational drift divided by the travelled distance, e.g. 0.01 for 1% drift.
+    /// Zero if nothing was travelled yet.
+    pub translation_per_distance: Scalar,
+    /// Rotational drift divided by the travelled distance, in radians per unit of distance.
+    pub rotation_per_distance: Scalar,
+}
+
+/// Chains relative poses together, keeping every intermediate pose and the travelled distance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseAccumulator
+{
+    poses: Vec<DualQuaternion>,
+    distance: Scalar,
+    renormalize: bool,
+}
+
+impl PoseAccumulator
+{
+    /// Start accumulating at `start`.
+    pub fn new(start: DualQuaternion) -> Self
+    {
+        PoseAccumulator { poses: vec![start], distance: 0.0, renormalize: true }
+    }
+
+    /// Whether the accumulated pose gets renormalized after every step, so rounding errors don't pile up (enabled by default).
+    /// See `DualQuaternion::renormalized()`.
+    pub fn with_renormalization(mut self, renormalize: bool) -> Self
+    {
+        self.renormalize = renormalize;
+        self
+    }
+
+    /// Move by `relative` in the frame of the current pose and return the new pose.
+    pub fn push(&mut self, relative: &DualQuaternion) -> DualQuaternion
+    {
+        let previous = self.current();
+        let mut next = previous * relative;
+
+        if self.renormalize {
+            next = next.renormalized();
+        }
+
+        self.distance += previous.distance_to(&next, 0.0, 1.0);
+        self.poses.push(next);
+
+        next
+    }
+
+    /// The latest pose.
+    pub fn current(&self) -> DualQuaternion
+    {
+        self.poses[self.poses.len() - 1]
+    }
+
+    /// All poses, starting with the start pose.
+    pub fn poses(&self) -> &[DualQuaternion] { &self.poses }
+
+    /// Length of the path through all positions.
+    pub fn distance(&self) -> Scalar { self.distance }
+
+    /// Drift of the latest pose compared to the true pose `truth`.
+    pub fn drift(&self, truth: &DualQuaternion) -> Drift
+    {
+        let current = self.current();
+
+        let translation = current.distance_to(truth, 0.0, 1.0);
+        let rotation = current.distance_to(truth, 1.0, 0.0);
+
+        let per_distance = |x: Scalar| if self.distance > Scalar::EPSILON { x / self.distance } else { 0.0 };
+
+        Drift {
+            translation,
+            rotation: Angle::radians(rotation),
+            translation_per_distance: per_distance(translation),
+            rotation_per_distance: per_distance(rotation),
+        }
+    }
+}
+
+impl Extend<DualQuaternion> for PoseAccumulator
+{
+    fn extend<T: IntoIterator<Item = DualQuaternion>>(&mut self, relatives: T)
+    {
+        for relative in relatives
+        {
+            self.push(&relative);
+        }
+    }
+}
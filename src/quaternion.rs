@@ -2,8 +2,11 @@
 
 
 pub use crate::angle::Angle;
+pub use crate::complex::Complex;
 use crate::vector3::Vector3;
-use crate::util::Scalar;
+use crate::util::{Scalar, write_terms};
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
 
 #[repr(C)]
 #[derive(
@@ -19,44 +22,16 @@ pub struct Quaternion
     pub k: Scalar,
 }
 
-impl std::fmt::Display for Quaternion
+/// Embeds the complex plane as `w + xi`, which keeps products intact.
+impl From<Complex> for Quaternion
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.w.powi(2) > Scalar::EPSILON {
-            write!(f, "{}",  self.w);
-
-            if self.i.powi(2) > Scalar::EPSILON ||
-                self.j.powi(2) > Scalar::EPSILON ||
-                self.k.powi(2) > Scalar::EPSILON
-            {
-                write!(f, " + ");
-            }
-        }
-
-        if self.i.powi(2) > Scalar::EPSILON {
-            write!(f, "{}i", self.i);
-
-            if self.j.powi(2) > Scalar::EPSILON ||
-                self.k.powi(2) > Scalar::EPSILON
-            {
-                write!(f, " + ");
-            }
-        }
-
-        if self.j.powi(2) > Scalar::EPSILON {
-            write!(f, "{}j", self.j);
-
-            if self.k.powi(2) > Scalar::EPSILON
-            {
-                write!(f, " + ");
-            }
-        }
-
-        if self.k.powi(2) > Scalar::EPSILON {
-            write!(f, "{}k", self.k);
-        }
+    fn from(value: Complex) -> Self { Quaternion { w: value.re, i: value.im, j: 0.0, k: 0.0 } }
+}
 
-        write!(f, "")
+impl core::fmt::Display for Quaternion
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_terms(f, &[(self.w, ""), (self.i, "i"), (self.j, "j"), (self.k, "k")])
     }
 }
 
@@ -91,6 +66,22 @@ impl Quaternion
         Quaternion { w: 0.0, i: pos[0], j: pos[1], k: pos[2] }
     }
 
+    /// The part `w + xi`, i.e. the inverse of the embedding `From<Complex>`.
+    pub fn complex_part(&self) -> Complex
+    {
+        Complex { re: self.w, im: self.i }
+    }
+
+    /// Project onto the complex plane `w + t·axis`, where `axis` gets normalized and plays the role of `i`.
+    /// For rotors, normalizing the result gives `cos(x/2) + i sin(x/2)`, where x is the angle of
+    /// the rotation around `axis` (its twist), e.g. to extract the yaw of a rotation.
+    pub fn project_onto_plane(&self, axis: &[Scalar]) -> Complex
+    {
+        let axis = Vector3 { x: axis[0], y: axis[1], z: axis[2] }.normalize();
+
+        Complex { re: self.w, im: axis.dot(&Vector3 { x: self.i, y: self.j, z: self.k }) }
+    }
+
     /// Create a rotor, i.e. a normalized quaternion used for rotating
     pub fn rotor(angle: Angle, axis: &[Scalar]) -> Self
     {
@@ -115,6 +106,36 @@ impl Quaternion
         scale.sqrt() * q
     }
 
+    /// Create a rotor from the columns of a rotation matrix, i.e. the rotated x-, y- and z-axis.
+    /// The columns must be orthonormal.
+    pub(crate) fn from_rotation_columns(x: Vector3, y: Vector3, z: Vector3) -> Self
+    {
+        // https://www.euclideanspace.com/maths/geometry/rotations/conversions/matrixToQuaternion/
+        //
+        // Choose the biggest component first to avoid dividing by (almost) zero
+
+        let trace = x.x + y.y + z.z;
+
+        let q = if trace > 0.0 {
+            let s = 2.0 * (1.0 + trace).sqrt();
+            Quaternion { w: 0.25 * s, i: (y.z - z.y) / s, j: (z.x - x.z) / s, k: (x.y - y.x) / s }
+        }
+        else if x.x > y.y && x.x > z.z {
+            let s = 2.0 * (1.0 + x.x - y.y - z.z).sqrt();
+            Quaternion { w: (y.z - z.y) / s, i: 0.25 * s, j: (y.x + x.y) / s, k: (z.x + x.z) / s }
+        }
+        else if y.y > z.z {
+            let s = 2.0 * (1.0 + y.y - x.x - z.z).sqrt();
+            Quaternion { w: (z.x - x.z) / s, i: (y.x + x.y) / s, j: 0.25 * s, k: (z.y + y.z) / s }
+        }
+        else {
+            let s = 2.0 * (1.0 + z.z - x.x - y.y).sqrt();
+            Quaternion { w: (x.y - y.x) / s, i: (z.x + x.z) / s, j: (z.y + y.z) / s, k: 0.25 * s }
+        };
+
+        q.normalized()
+    }
+
     /// Rotate a vector.
     /// <div class="warning">
     /// If you want to use unnormalized quaternions for scaled rotation, consider `Quaternion::transform_vector_scaled()`
@@ -207,19 +228,17 @@ impl Quaternion
         let radius = w.exp();
         let angle  = (i*i + j*j + k*k).sqrt();
 
-        // exp(0.0) = 1
-        // Without this check, it won't work
-        if angle*angle < Scalar::EPSILON {
-            return Quaternion::ONE
-        }
-
         let (sin,cos) = angle.sin_cos();
 
+        // sin(angle)/angle, with its power series for small angles.
+        // Without this, we would divide by zero for real quaternions, i.e. exp(w) = e^w
+        let sinc = if angle*angle < Scalar::EPSILON { 1.0 - angle*angle / 6.0 } else { sin / angle };
+
         Quaternion {
             w: radius * cos,
-            i: radius * sin * i / angle,
-            j: radius * sin * j / angle,
-            k: radius * sin * k / angle,
+            i: radius * sinc * i,
+            j: radius * sinc * j,
+            k: radius * sinc * k,
         }
 
     }
@@ -234,17 +253,171 @@ impl Quaternion
         let norm = self.norm();
 
         let axis_norm = (i*i + j*j + k*k).sqrt();
-        let acos = (w / axis_norm).acos();
+
+        // The angle divided by axis_norm. Close to a real quaternion (relative to its norm) this is 1/w
+        // up to second order, which avoids dividing by zero. Real quaternions have no axis, so their vector part stays zero.
+        let factor = if axis_norm <= Scalar::EPSILON * norm {
+            if w > 0.0 { 1.0 / w } else { 0.0 }
+        } else {
+            axis_norm.atan2(w) / axis_norm
+        };
 
         Quaternion {
             w: norm.ln(),
-            i: acos * i / axis_norm,
-            j: acos * j / axis_norm,
-            k: acos * k / axis_norm,
+            i: factor * i,
+            j: factor * j,
+            k: factor * k,
+        }
+
+    }
+
+    /// `n` near-uniformly distributed rotors, generated deterministically (i.e. without random numbers).
+    /// Useful for sampling orientations, e.g. for template matching.
+    pub fn super_fibonacci(n: usize) -> impl Iterator<Item = Quaternion>
+    {
+        // Alexa, "Super-Fibonacci Spirals: Fast, Low-Discrepancy Sampling of SO(3)", 2022
+        //
+        // Computed in f64, since the angles grow with n and f32 would lose the fractional part quickly
+
+        const PHI: f64 = core::f64::consts::SQRT_2;
+        const PSI: f64 = 1.533_751_168_755_204_3;
+
+        (0..n).map(move |i| {
+            let s = i as f64 + 0.5;
+            let t = s / n as f64;
+
+            let (r, big_r) = (t.sqrt(), (1.0 - t).sqrt());
+            let (alpha, beta) = (core::f64::consts::TAU * s / PHI, core::f64::consts::TAU * s / PSI);
+
+            Quaternion {
+                w: (r * alpha.sin()) as Scalar,
+                i: (r * alpha.cos()) as Scalar,
+                j: (big_r * beta.sin()) as Scalar,
+                k: (big_r * beta.cos()) as Scalar,
+            }
+        })
+    }
+
+    /// The rotor which best rotates the first direction of every pair onto the second one (Wahba's problem),
+    /// e.g. from body directions onto reference directions. `weights` has one weight per pair.
+    /// The directions don't need to be normalized, but their lengths act as additional weights.
+    /// Panics if `pairs` and `weights` don't have the same length.
+    pub fn from_direction_pairs(pairs: &[([Scalar; 3], [Scalar; 3])], weights: &[Scalar]) -> Self
+    {
+        assert!(pairs.len() == weights.len(), "every pair needs a weight");
+
+        // Davenport's q-method: the best rotor is the eigenvector with the largest eigenvalue
+        // of the symmetric 4x4 matrix K built from the attitude profile matrix S = Σ w a bᵀ.
+        // This is the same matrix as in Horn, "Closed-form solution of absolute orientation using unit quaternions", 1987.
+
+        let mut s = [[0.0 as Scalar; 3]; 3];
+        for ((a, b), w) in pairs.iter().zip(weights)
+        {
+            for (row, a) in a.iter().enumerate()
+            {
+                for (col, b) in b.iter().enumerate()
+                {
+                    s[row][col] += w * a * b;
+                }
+            }
+        }
+
+        let [[xx, xy, xz], [yx, yy, yz], [zx, zy, zz]] = s;
+
+        let k = [
+            [xx + yy + zz, yz - zy,       zx - xz,       xy - yx      ],
+            [yz - zy,      xx - yy - zz,  xy + yx,       zx + xz      ],
+            [zx - xz,      xy + yx,      -xx + yy - zz,  yz + zy      ],
+            [xy - yx,      zx + xz,       yz + zy,      -xx - yy + zz ],
+        ];
+
+        let (values, vectors) = symmetric_eigen4(k);
+
+        let best = (0..4).fold(0, |best, i| if values[i] > values[best] { i } else { best });
+        let [w, i, j, k] = vectors[best];
+
+        Quaternion { w, i, j, k }.normalized()
+    }
+
+    /// The rotor which rotates `a_body` exactly onto `a_ref` and `b_body` as close as possible onto `b_ref` (TRIAD).
+    /// Put the more accurate observation into `a`. The directions don't need to be normalized, but mustn't be parallel.
+    pub fn from_two_vector_pairs(a_body: &[Scalar], a_ref: &[Scalar], b_body: &[Scalar], b_ref: &[Scalar]) -> Self
+    {
+        // Both pairs span an orthonormal triad t1 = a, t2 = a × b, t3 = t1 × t2.
+        // The rotation maps the body triad onto the reference triad, i.e. R = T_ref * T_bodyᵀ
+
+        let triad = |a: &[Scalar], b: &[Scalar]| {
+            let a = Vector3 { x: a[0], y: a[1], z: a[2] }.normalize();
+            let b = Vector3 { x: b[0], y: b[1], z: b[2] };
+
+            let t2 = a.cross(&b).normalize();
+
+            [a, t2, a.cross(&t2)]
+        };
+
+        let body = triad(a_body, b_body);
+        let reference = triad(a_ref, b_ref);
+
+        let column = |axis: usize| {
+            let component = |v: &Vector3| [v.x, v.y, v.z][axis];
+
+            (0..3).fold(Vector3::ZERO, |acc, t| acc + component(&body[t]) * reference[t])
+        };
+
+        Self::from_rotation_columns(column(0), column(1), column(2))
+    }
+
+}
+
+/// Eigenvalues and eigenvectors of a symmetric 4x4 matrix, using the cyclic Jacobi method.
+fn symmetric_eigen4(mut a: [[Scalar; 4]; 4]) -> ([Scalar; 4], [[Scalar; 4]; 4])
+{
+    // v accumulates the rotations, so its rows end up being the eigenvectors
+    let mut v = [[0.0 as Scalar; 4]; 4];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for _sweep in 0..32
+    {
+        let off: Scalar = (0..4).flat_map(|p| (p+1..4).map(move |q| (p, q))).map(|(p, q)| a[p][q] * a[p][q]).sum();
+        if off < Scalar::EPSILON * Scalar::EPSILON {
+            break
         }
 
+        for p in 0..4
+        {
+            for q in p+1..4
+            {
+                if a[p][q].abs() < Scalar::MIN_POSITIVE {
+                    continue
+                }
+
+                // Rotate in the (p,q)-plane so a[p][q] becomes zero
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut()
+                {
+                    let (arp, arq) = (row[p], row[q]);
+                    row[p] = c * arp - s * arq;
+                    row[q] = s * arp + c * arq;
+                }
+
+                let (ap, aq) = (a[p], a[q]);
+                a[p] = core::array::from_fn(|r| c * ap[r] - s * aq[r]);
+                a[q] = core::array::from_fn(|r| s * ap[r] + c * aq[r]);
+
+                let (vp, vq) = (v[p], v[q]);
+                v[p] = core::array::from_fn(|r| c * vp[r] - s * vq[r]);
+                v[q] = core::array::from_fn(|r| s * vp[r] + c * vq[r]);
+            }
+        }
     }
 
+    ([a[0][0], a[1][1], a[2][2], a[3][3]], v)
 }
 
 auto_ops::impl_op_ex!(* |lhs: &Quaternion, rhs: &Quaternion| -> Quaternion {
@@ -0,0 +1,104 @@
+//! Rays, i.e. half-lines starting at an origin, e.g. for picking and raycasting.
+
+pub use crate::dual_quaternion::DualQuaternion;
+pub use crate::line::Line;
+pub use crate::plane::Plane;
+
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+
+/// All points `origin + t * dir` with `t >= 0`. The direction is normalized, so `t` is the distance to the origin.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Ray
+{
+    pub origin: [Scalar; 3],
+    pub dir:    [Scalar; 3],
+}
+
+impl From<Ray> for Line
+{
+    fn from(ray: Ray) -> Self { ray.line() }
+}
+
+impl Ray
+{
+    /// Create a ray starting at `origin` going in direction `dir`.
+    /// The direction will be normalized.
+    pub fn new(origin: &[Scalar], dir: &[Scalar]) -> Self
+    {
+        let dir = Vector3 { x: dir[0], y: dir[1], z: dir[2] }.normalize();
+
+        Ray { origin: [origin[0], origin[1], origin[2]], dir: dir.into() }
+    }
+
+    /// Create a ray starting at `from` going through `to`.
+    pub fn from_points(from: &[Scalar], to: &[Scalar]) -> Self
+    {
+        Self::new(from, &[to[0] - from[0], to[1] - from[1], to[2] - from[2]])
+    }
+
+    fn origin(&self) -> Vector3 { Vector3::from(self.origin) }
+
+    fn direction(&self) -> Vector3 { Vector3::from(self.dir) }
+
+    /// The point at distance `t` from the origin.
+    pub fn at(&self, t: Scalar) -> [Scalar; 3]
+    {
+        (self.origin() + t * self.direction()).into()
+    }
+
+    /// The infinite line containing this ray.
+    pub fn line(&self) -> Line
+    {
+        Line { dir: self.dir, moment: self.origin().cross(&self.direction()).into() }
+    }
+
+    /// The same ray, moved by `motor`, e.g. from object into world space.
+    /// Only works on normalized dual quaternions.
+    pub fn transform(&self, motor: &DualQuaternion) -> Self
+    {
+        Ray { origin: motor.transform_point(&self.origin), dir: motor.transform_vector3(&self.dir) }
+    }
+
+    /// The point on this ray nearest to `point`.
+    pub fn closest_point(&self, point: &[Scalar]) -> [Scalar; 3]
+    {
+        let t = self.direction().dot(&(Vector3 { x: point[0], y: point[1], z: point[2] } - self.origin()));
+
+        self.at(t.max(0.0))
+    }
+
+    /// The points on this ray and on `line`, which are closest to each other.
+    /// Returns `None` if they are parallel.
+    pub fn closest_points_to_line(&self, line: &Line) -> Option<([Scalar; 3], [Scalar; 3])>
+    {
+        let (on_ray, _) = self.line().closest_points_between_lines(line)?;
+
+        // If the nearest point of the infinite line lies behind the origin, the origin is the nearest point of the ray
+        let t = self.direction().dot(&(Vector3::from(on_ray) - self.origin()));
+
+        if t >= 0.0 {
+            let on_line = line.closest_point(&on_ray);
+            return Some((on_ray, on_line))
+        }
+
+        Some((self.origin, line.closest_point(&self.origin)))
+    }
+
+    /// Distance from the origin to where this ray hits `plane`, use `.at()` to get the point.
+    /// Returns `None` if the ray is parallel to the plane or points away from it.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<Scalar>
+    {
+        let normal = Vector3::from(plane.normal);
+        let denom = normal.dot(&self.direction());
+
+        if denom.abs() < Scalar::EPSILON {
+            return None
+        }
+
+        let t = -plane.signed_distance(&self.origin) / denom;
+
+        if t < 0.0 { None } else { Some(t) }
+    }
+}
@@ -0,0 +1,132 @@
+//! A rigid body's pose and velocity, integrated over time from the forces and torques acting on it.
+//!
+//! Velocities and accumulated wrenches are kept in the body frame, whose origin has to be the center of mass
+//! and whose axes have to be the principal axes of inertia.
+
+pub use crate::screw::{DualQuaternion, Twist, Wrench};
+
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+
+/// When the pose gets renormalized, to keep rounding errors from turning it into a non-rigid transformation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Renormalization
+{
+    Never,
+    Always,
+    /// Renormalize after every n-th step
+    Every(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RigidState
+{
+    /// Maps body coordinates to world coordinates.
+    pub pose: DualQuaternion,
+    /// Velocity in the body frame.
+    pub twist: Twist,
+    pub mass: Scalar,
+    /// Principal moments of inertia around the body's x, y and z axes.
+    pub inertia: [Scalar; 3],
+    pub renormalization: Renormalization,
+    /// Sum of all wrenches applied since the last step, in the body frame.
+    wrench: Wrench,
+    steps: u32,
+}
+
+impl RigidState
+{
+    /// A body at rest at `pose`.
+    pub fn new(pose: DualQuaternion, mass: Scalar, inertia: [Scalar; 3]) -> Self
+    {
+        RigidState {
+            pose,
+            twist: Twist::ZERO,
+            mass,
+            inertia,
+            renormalization: Renormalization::Always,
+            wrench: Wrench::ZERO,
+            steps: 0,
+        }
+    }
+
+    /// The wrench accumulated for the next step, in the body frame.
+    pub fn accumulated_wrench(&self) -> Wrench { self.wrench }
+
+    /// Add a wrench given in the body frame.
+    pub fn apply_body_wrench(&mut self, wrench: &Wrench)
+    {
+        self.wrench += wrench;
+    }
+
+    /// Add a wrench given in the world frame, i.e. with its torque about the world origin.
+    pub fn apply_world_wrench(&mut self, wrench: &Wrench)
+    {
+        self.wrench += wrench.transformed(&self.pose.inverse());
+    }
+
+    /// Add a world force acting at the world point `point`.
+    pub fn apply_force_at(&mut self, force: &[Scalar], point: &[Scalar])
+    {
+        self.apply_world_wrench(&Wrench::from_force_at(force, point));
+    }
+
+    /// Add a world force acting at the center of mass, e.g. gravity.
+    pub fn apply_central_force(&mut self, force: &[Scalar])
+    {
+        self.apply_force_at(force, &self.pose.translation());
+    }
+
+    pub fn kinetic_energy(&self) -> Scalar
+    {
+        let [wx, wy, wz] = self.twist.angular;
+        let v = Vector3::from(self.twist.linear);
+
+        0.5 * (self.mass * v.norm_squared() + self.inertia[0]*wx*wx + self.inertia[1]*wy*wy + self.inertia[2]*wz*wz)
+    }
+
+    /// Advance by `dt` seconds with the semi-implicit (symplectic) Euler method and clear the accumulated wrench.
+    /// The velocity gets updated first and the pose moves with the new velocity.
+    pub fn step(&mut self, dt: Scalar)
+    {
+        // Newton-Euler equations in the body frame:
+        //   I dω/dt = τ - ω × Iω
+        //   m dv/dt = f - m ω × v
+
+        let w = Vector3::from(self.twist.angular);
+        let v = Vector3::from(self.twist.linear);
+        let i = Vector3::from(self.inertia);
+
+        let momentum = Vector3 { x: i.x * w.x, y: i.y * w.y, z: i.z * w.z };
+        let torque = Vector3::from(self.wrench.torque) - w.cross(&momentum);
+        let force  = Vector3::from(self.wrench.force) * (1.0 / self.mass) - w.cross(&v);
+
+        let w = w + dt * Vector3 { x: torque.x / i.x, y: torque.y / i.y, z: torque.z / i.z };
+        let v = v + dt * force;
+
+        self.twist = Twist::new(w.into(), v.into());
+
+        // The twist is in the body frame, so it's applied from the right
+        self.pose = self.pose * self.twist.exp(dt);
+
+        self.wrench = Wrench::ZERO;
+        self.steps = self.steps.wrapping_add(1);
+
+        let renormalize = match self.renormalization
+        {
+            Renormalization::Never    => false,
+            Renormalization::Always   => true,
+            Renormalization::Every(n) => n != 0 && self.steps.is_multiple_of(n),
+        };
+
+        if renormalize {
+            self.renormalize();
+        }
+    }
+
+    /// Make the pose a rigid transformation again, i.e. a normalized real part orthogonal to the dual part.
+    pub fn renormalize(&mut self)
+    {
+        self.pose = self.pose.renormalized();
+    }
+}
@@ -0,0 +1,88 @@
+//! Rotations in the plane. Unit complex numbers rotate other complex numbers by multiplication,
+//! this type uses that to rotate 2D points.
+
+use crate::complex::{Complex, Angle};
+use crate::vector2::Vector2;
+use crate::util::Scalar;
+
+/// A rotation in the plane, stored as unit complex number.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Rotor2D
+{
+    pub complex: Complex,
+}
+
+impl From<Rotor2D> for Complex
+{
+    fn from(rotor: Rotor2D) -> Self { rotor.complex }
+}
+
+impl Rotor2D
+{
+    /// No rotation
+    pub const IDENTITY: Rotor2D = Rotor2D { complex: Complex::ONE };
+
+    /// Create a rotor rotating counter-clockwise by `angle`.
+    pub fn from_angle(angle: Angle) -> Self
+    {
+        Rotor2D { complex: Complex::from_angle(angle) }
+    }
+
+    /// Create a rotor from a complex number. It will be normalized.
+    pub fn from_complex(complex: Complex) -> Self
+    {
+        Rotor2D { complex: complex.normalized() }
+    }
+
+    /// Create a rotor from a column-major 2x2 rotation matrix, i.e. `matrix[column][row]`.
+    /// Only the first column is used, so the matrix must be a rotation matrix.
+    pub fn from_matrix2(matrix: &[[Scalar; 2]; 2]) -> Self
+    {
+        Self::from_complex(Complex { re: matrix[0][0], im: matrix[0][1] })
+    }
+
+    /// Column-major 2x2 rotation matrix of this rotor, i.e. `matrix[column][row]`.
+    pub fn to_matrix2(&self) -> [[Scalar; 2]; 2]
+    {
+        let Complex { re: cos, im: sin } = self.complex;
+
+        [[cos, sin], [-sin, cos]]
+    }
+
+    /// The angle this rotor rotates by, in (-180°,180°].
+    pub fn angle(&self) -> Angle
+    {
+        self.complex.angle()
+    }
+
+    /// The rotor rotating in the opposite direction.
+    pub fn inverse(&self) -> Self
+    {
+        Rotor2D { complex: self.complex.conj() }
+    }
+
+    /// Rotate a 2D point around the origin.
+    pub fn rotate_point2(&self, point: &[Scalar]) -> [Scalar; 2]
+    {
+        self.complex.rotate(&Vector2 { x: point[0], y: point[1] }).into()
+    }
+
+    /// Rotate a 2D vector around the origin.
+    pub fn rotate_vector2(&self, vector: &Vector2) -> Vector2
+    {
+        self.complex.rotate(vector)
+    }
+
+    /// Spherically interpolate between `self` and `other` along the shorter arc.
+    pub fn slerp(&self, other: &Rotor2D, alpha: Scalar) -> Rotor2D
+    {
+        // The rotor taking self to other, scaled down by alpha
+        let delta = (other.complex * self.complex.conj()).angle();
+
+        Rotor2D::from_angle(delta * alpha) * self
+    }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &Rotor2D, rhs: &Rotor2D| -> Rotor2D { Rotor2D { complex: lhs.complex * rhs.complex } });
+auto_ops::impl_op_ex!(*= |lhs: &mut Rotor2D, rhs: &Rotor2D| { lhs.complex = lhs.complex * rhs.complex });
@@ -0,0 +1,138 @@
+//! Twists (velocities) and wrenches (forces) of rigid bodies from screw theory.
+//!
+//! Both are given in some frame. The linear velocity of a twist is the velocity of the body point
+//! which currently is at the frame's origin, and the torque of a wrench is about the frame's origin.
+
+pub use crate::dual_quaternion::DualQuaternion;
+
+use crate::vector3::Vector3;
+use crate::util::Scalar;
+
+/// Angular and linear velocity of a rigid body.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Twist
+{
+    pub angular: [Scalar; 3],
+    pub linear:  [Scalar; 3],
+}
+
+/// Torque and force acting on a rigid body.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Wrench
+{
+    pub torque: [Scalar; 3],
+    pub force:  [Scalar; 3],
+}
+
+/// Rotate both vectors by the motor and add `translation X first` to the second one.
+/// Twists and wrenches change frames the same way.
+fn transform_pair(motor: &DualQuaternion, first: &[Scalar; 3], second: &[Scalar; 3]) -> ([Scalar; 3], [Scalar; 3])
+{
+    let first  = Vector3::from(motor.transform_vector3(first));
+    let second = Vector3::from(motor.transform_vector3(second));
+    let t = Vector3::from(motor.translation());
+
+    (first.into(), (second + t.cross(&first)).into())
+}
+
+impl Twist
+{
+    pub const ZERO: Twist = Twist { angular: [0.0; 3], linear: [0.0; 3] };
+
+    pub fn new(angular: [Scalar; 3], linear: [Scalar; 3]) -> Self
+    {
+        Twist { angular, linear }
+    }
+
+    /// The same velocity, given in the frame `motor` maps this frame to.
+    /// Only works on normalized dual quaternions.
+    pub fn transformed(&self, motor: &DualQuaternion) -> Self
+    {
+        let (angular, linear) = transform_pair(motor, &self.angular, &self.linear);
+
+        Twist { angular, linear }
+    }
+
+    /// Power of the wrench acting on a body moving with this twist (= reciprocal product).
+    /// It's zero if the wrench does no work on the twist.
+    pub fn reciprocal(&self, wrench: &Wrench) -> Scalar
+    {
+        let dot = |a: &[Scalar; 3], b: &[Scalar; 3]| a[0]*b[0] + a[1]*b[1] + a[2]*b[2];
+
+        dot(&self.angular, &wrench.torque) + dot(&self.linear, &wrench.force)
+    }
+
+    /// The motor resulting from moving with this twist for the time `dt`.
+    /// Apply it from the left to a pose given in the same frame as this twist.
+    pub fn exp(&self, dt: Scalar) -> DualQuaternion
+    {
+        // Same as in DualQuaternion::screw(), the half angle and half translation are needed
+        let (w, v) = (0.5 * dt * Vector3::from(self.angular), 0.5 * dt * Vector3::from(self.linear));
+
+        DualQuaternion { w: 0.0, i: w.x, j: w.y, k: w.z, ie: v.x, je: v.y, ke: v.z, we: 0.0 }.exp()
+    }
+
+    /// The twist which moves from the identity to `motor` in unit time, i.e. the inverse of `Twist::exp()`.
+    /// Only works on normalized dual quaternions.
+    pub fn log(motor: &DualQuaternion) -> Self
+    {
+        let log = motor.log();
+
+        Twist {
+            angular: [2.0 * log.i,  2.0 * log.j,  2.0 * log.k],
+            linear:  [2.0 * log.ie, 2.0 * log.je, 2.0 * log.ke],
+        }
+    }
+}
+
+impl Wrench
+{
+    pub const ZERO: Wrench = Wrench { torque: [0.0; 3], force: [0.0; 3] };
+
+    pub fn new(torque: [Scalar; 3], force: [Scalar; 3]) -> Self
+    {
+        Wrench { torque, force }
+    }
+
+    /// Wrench of a force acting at `point`.
+    pub fn from_force_at(force: &[Scalar], point: &[Scalar]) -> Self
+    {
+        let f = Vector3 { x: force[0], y: force[1], z: force[2] };
+        let p = Vector3 { x: point[0], y: point[1], z: point[2] };
+
+        Wrench { torque: p.cross(&f).into(), force: f.into() }
+    }
+
+    /// The same wrench, given in the frame `motor` maps this frame to.
+    /// Only works on normalized dual quaternions.
+    pub fn transformed(&self, motor: &DualQuaternion) -> Self
+    {
+        let (force, torque) = transform_pair(motor, &self.force, &self.torque);
+
+        Wrench { torque, force }
+    }
+
+    /// See `Twist::reciprocal()`
+    pub fn reciprocal(&self, twist: &Twist) -> Scalar
+    {
+        twist.reciprocal(self)
+    }
+}
+
+fn add(a: &[Scalar; 3], b: &[Scalar; 3]) -> [Scalar; 3] { [a[0] + b[0], a[1] + b[1], a[2] + b[2]] }
+fn sub(a: &[Scalar; 3], b: &[Scalar; 3]) -> [Scalar; 3] { [a[0] - b[0], a[1] - b[1], a[2] - b[2]] }
+fn scale(a: &[Scalar; 3], s: Scalar)     -> [Scalar; 3] { [a[0] * s, a[1] * s, a[2] * s] }
+
+auto_ops::impl_op_ex!(+ |lhs: &Twist, rhs: &Twist| -> Twist { Twist { angular: add(&lhs.angular, &rhs.angular), linear: add(&lhs.linear, &rhs.linear) } });
+auto_ops::impl_op_ex!(- |lhs: &Twist, rhs: &Twist| -> Twist { Twist { angular: sub(&lhs.angular, &rhs.angular), linear: sub(&lhs.linear, &rhs.linear) } });
+auto_ops::impl_op_ex!(+= |lhs: &mut Twist, rhs: &Twist| { *lhs = *lhs + rhs });
+auto_ops::impl_op_ex!(- |t: &Twist| -> Twist { Twist { angular: scale(&t.angular, -1.0), linear: scale(&t.linear, -1.0) } });
+auto_ops::impl_op_ex_commutative!(* |lhs: &Twist, rhs: &Scalar| -> Twist { Twist { angular: scale(&lhs.angular, *rhs), linear: scale(&lhs.linear, *rhs) } });
+
+auto_ops::impl_op_ex!(+ |lhs: &Wrench, rhs: &Wrench| -> Wrench { Wrench { torque: add(&lhs.torque, &rhs.torque), force: add(&lhs.force, &rhs.force) } });
+auto_ops::impl_op_ex!(- |lhs: &Wrench, rhs: &Wrench| -> Wrench { Wrench { torque: sub(&lhs.torque, &rhs.torque), force: sub(&lhs.force, &rhs.force) } });
+auto_ops::impl_op_ex!(+= |lhs: &mut Wrench, rhs: &Wrench| { *lhs = *lhs + rhs });
+auto_ops::impl_op_ex!(- |w: &Wrench| -> Wrench { Wrench { torque: scale(&w.torque, -1.0), force: scale(&w.force, -1.0) } });
+auto_ops::impl_op_ex_commutative!(* |lhs: &Wrench, rhs: &Scalar| -> Wrench { Wrench { torque: scale(&lhs.torque, *rhs), force: scale(&lhs.force, *rhs) } });
@@ -0,0 +1,109 @@
+//! Similarity transformations, i.e. rigid transformations with an additional uniform scale.
+
+pub use crate::dual_quaternion::{DualQuaternion, Quaternion};
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+/// First scales by `scale` (around the origin), then applies the normalized `motor`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Similarity
+{
+    pub motor: DualQuaternion,
+    pub scale: Scalar,
+}
+
+impl From<DualQuaternion> for Similarity
+{
+    fn from(motor: DualQuaternion) -> Self { Similarity { motor, scale: 1.0 } }
+}
+
+/// The same rotation as `motor`, but with its translation multiplied by `factor`.
+fn scale_translation(motor: &DualQuaternion, factor: Scalar) -> DualQuaternion
+{
+    DualQuaternion {
+        ie: factor * motor.ie,
+        je: factor * motor.je,
+        ke: factor * motor.ke,
+        we: factor * motor.we,
+        ..*motor
+    }
+}
+
+impl Similarity
+{
+    pub const IDENTITY: Similarity = Similarity { motor: DualQuaternion::ONE, scale: 1.0 };
+
+    pub fn new(motor: DualQuaternion, scale: Scalar) -> Self
+    {
+        Similarity { motor, scale }
+    }
+
+    /// Create a similarity from a column-major 4x4 matrix (i.e. `matrix[column][row]`) with a uniform scale.
+    /// Matrices with non-uniform scale or reflections will produce wrong results.
+    pub fn from_matrix4(matrix: &[[Scalar; 4]; 4]) -> Self
+    {
+        let column_norm = |c: usize| (matrix[c][0].powi(2) + matrix[c][1].powi(2) + matrix[c][2].powi(2)).sqrt();
+        let scale = (column_norm(0) + column_norm(1) + column_norm(2)) / 3.0;
+
+        // The polar decomposition in there removes the scale
+        Similarity { motor: DualQuaternion::from_matrix4_renormalized(matrix), scale }
+    }
+
+    /// Column-major 4x4 matrix (i.e. `matrix[column][row]`) of this similarity in homogeneous coordinates.
+    pub fn to_matrix4(&self) -> [[Scalar; 4]; 4]
+    {
+        let mut matrix = self.motor.to_matrix4();
+
+        for column in matrix.iter_mut().take(3)
+        {
+            for x in column.iter_mut().take(3)
+            {
+                *x *= self.scale;
+            }
+        }
+
+        matrix
+    }
+
+    /// The inverse similarity.
+    /// May produce invalid numbers if the scale is 0.0
+    pub fn inverse(&self) -> Self
+    {
+        // x -> s R x + t  is inverted by  y -> (1/s) R^-1 y - (1/s) R^-1 t,
+        // i.e. the inverse motor with its translation scaled by 1/s
+        Similarity { motor: scale_translation(&self.motor.conj(), 1.0 / self.scale), scale: 1.0 / self.scale }
+    }
+
+    pub fn transform_point(&self, point: &[Scalar]) -> [Scalar; 3]
+    {
+        self.motor.transform_point(&[self.scale * point[0], self.scale * point[1], self.scale * point[2]])
+    }
+
+    /// Rotate and scale a vector, without translating it.
+    pub fn transform_vector3(&self, vector: &[Scalar]) -> [Scalar; 3]
+    {
+        self.motor.transform_vector3(&[self.scale * vector[0], self.scale * vector[1], self.scale * vector[2]])
+    }
+
+    /// Interpolate motor (with sclerp) and scale (geometrically) independently.
+    pub fn interpolate(&self, other: &Similarity, alpha: Scalar) -> Self
+    {
+        Similarity {
+            motor: self.motor.sclerp(&other.motor.aligned_with(&self.motor), alpha),
+            scale: self.scale * (other.scale / self.scale).powf(alpha),
+        }
+    }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &Similarity, rhs: &Similarity| -> Similarity {
+    // s1 R1 (s2 R2 x + t2) + t1 = s1 s2 R1 R2 x + (s1 R1 t2 + t1),
+    // so the right hand side's translation gets scaled by the left hand side's scale
+    Similarity {
+        motor: lhs.motor * scale_translation(&rhs.motor, lhs.scale),
+        scale: lhs.scale * rhs.scale,
+    }
+});
+auto_ops::impl_op_ex!(*= |lhs: &mut Similarity, rhs: &Similarity| { *lhs = *lhs * rhs });
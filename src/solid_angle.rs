@@ -0,0 +1,86 @@
+//! Solid angles, e.g. the aperture of a light cone or the field of view of a sensor.
+
+pub use crate::angle::Angle;
+
+use crate::util::Scalar;
+
+#[cfg(not(feature = "use_f64"))]
+use core::f32::consts::TAU;
+
+#[cfg(feature = "use_f64")]
+use core::f64::consts::TAU;
+
+/// Solid angle in steradians. The whole sphere is 4π sr.
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, PartialOrd, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign
+)]
+pub struct SolidAngle
+{
+    sr: Scalar,
+}
+
+impl core::fmt::Display for SolidAngle
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} sr", self.sr)
+    }
+}
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &SolidAngle, rhs: &Scalar| -> SolidAngle {
+    SolidAngle { sr: lhs.sr * rhs }
+});
+
+auto_ops::impl_op_ex!(/ |lhs: &SolidAngle, rhs: &Scalar| -> SolidAngle {
+    SolidAngle { sr: lhs.sr / rhs }
+});
+
+impl SolidAngle
+{
+    /// The whole sphere, 4π sr
+    pub const SPHERE    : SolidAngle = SolidAngle { sr: 2.0 * TAU };
+
+    /// Half the sphere, 2π sr
+    pub const HEMISPHERE: SolidAngle = SolidAngle { sr: TAU };
+
+    pub const ZERO      : SolidAngle = SolidAngle { sr: 0.0 };
+
+    pub fn steradians(sr: Scalar) -> Self
+    {
+        SolidAngle { sr }
+    }
+
+    /// Get this solid angle in steradians
+    pub fn sr(&self) -> Scalar
+    {
+        self.sr
+    }
+
+    /// Solid angle of a cone with the given half angle (between axis and surface), i.e. 2π(1 - cos θ).
+    /// A half angle of 180° covers the whole sphere.
+    pub fn from_cone_half_angle(half_angle: Angle) -> Self
+    {
+        let half_angle = half_angle.abs().min(Angle::HALF);
+
+        SolidAngle { sr: TAU * (1.0 - half_angle.cos()) }
+    }
+
+    /// Half angle of the cone with this solid angle, in [0°,180°].
+    pub fn cone_half_angle(&self) -> Angle
+    {
+        Angle::acos(1.0 - self.sr / TAU)
+    }
+
+    /// Which part of the whole sphere this solid angle covers, e.g. 0.5 for a hemisphere.
+    pub fn fraction_of_sphere(&self) -> Scalar
+    {
+        self.sr / Self::SPHERE.sr
+    }
+
+    /// Solid angle covering `fraction` of the whole sphere.
+    pub fn from_fraction_of_sphere(fraction: Scalar) -> Self
+    {
+        Self::SPHERE * fraction
+    }
+}
@@ -0,0 +1,189 @@
+//! Smooth curves through rigid poses, e.g. for camera fly-throughs.
+
+pub use crate::dual_quaternion::DualQuaternion;
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Cubic Bézier curve through motors, i.e. de Casteljau's algorithm with sclerp instead of lerp.
+pub(crate) fn bezier(p: &[DualQuaternion; 4], alpha: Scalar) -> DualQuaternion
+{
+    let a = p[0].sclerp(&p[1], alpha);
+    let b = p[1].sclerp(&p[2], alpha);
+    let c = p[2].sclerp(&p[3], alpha);
+
+    let d = a.sclerp(&b, alpha);
+    let e = b.sclerp(&c, alpha);
+
+    d.sclerp(&e, alpha)
+}
+
+/// Catmull–Rom spline which passes exactly through all of its waypoints.
+///
+/// Each segment is a cubic Bézier curve in the space of motors, evaluated with repeated `sclerp`.
+/// The tension controls the length of the tangents: 0.0 is the classic Catmull–Rom spline,
+/// 1.0 gives zero tangents (i.e. the motion stops at every waypoint).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MotorCatmullRom
+{
+    waypoints: Vec<DualQuaternion>,
+    tension:   Scalar,
+}
+
+impl MotorCatmullRom
+{
+    /// Create a spline through the given waypoints, which should be normalized.
+    /// Every waypoint is flipped into the hemisphere of its predecessor, so the spline takes the shortest path.
+    pub fn new(waypoints: &[DualQuaternion], tension: Scalar) -> Self
+    {
+        let mut waypoints = waypoints.to_vec();
+
+        for i in 1..waypoints.len()
+        {
+            waypoints[i] = waypoints[i].aligned_with(&waypoints[i - 1]);
+        }
+
+        MotorCatmullRom { waypoints, tension }
+    }
+
+    pub fn waypoints(&self) -> &[DualQuaternion] { &self.waypoints }
+
+    pub fn tension(&self) -> Scalar { self.tension }
+
+    /// Number of segments, i.e. the parameter range of `.evaluate()` is [0, segments].
+    pub fn segments(&self) -> usize { self.waypoints.len().saturating_sub(1) }
+
+    /// Tangent at waypoint `i` in the logarithmic space local to that waypoint, for a segment taking `duration`.
+    /// `before` and `after` are the durations of the segments around the waypoint.
+    fn tangent(&self, i: usize, (before, after): (Scalar, Scalar), duration: Scalar) -> DualQuaternion
+    {
+        // The local steps to the neighbours are turned into velocities and averaged, each weighted by the
+        // other duration (non-uniform Catmull–Rom). For equal durations this is exactly (P_{i+1} - P_{i-1}) / 2
+        // for points. The end points reuse themselves as missing neighbours.
+
+        let p = &self.waypoints;
+        let prev = p[i.saturating_sub(1)];
+        let next = p[(i + 1).min(p.len() - 1)];
+
+        let incoming = (prev.conj() * p[i]).log();
+        let outgoing = (p[i].conj() * next).log();
+
+        let velocity = (1.0 / (before + after)) * ((after / before) * incoming + (before / after) * outgoing);
+
+        ((1.0 - self.tension) * duration) * velocity
+    }
+
+    /// Evaluate segment `i` (between waypoint `i` and `i+1`) at `alpha` in [0,1].
+    pub fn evaluate_segment(&self, i: usize, alpha: Scalar) -> DualQuaternion
+    {
+        self.evaluate_segment_with_durations(i, alpha, |_| 1.0)
+    }
+
+    /// Evaluate segment `i` at `alpha` in [0,1], where segment `j` takes `duration(j)`, e.g. the time between two keyframes.
+    /// The tangents are scaled by the durations, so the velocity doesn't jump at unevenly spaced waypoints.
+    pub(crate) fn evaluate_segment_with_durations(&self, i: usize, alpha: Scalar, duration: impl Fn(usize) -> Scalar) -> DualQuaternion
+    {
+        // Durations of the segments around waypoint j, the end points only have one
+        let around = |j: usize| (duration(j.saturating_sub(1)), duration(j.min(self.segments() - 1)));
+
+        let (p0, p3) = (self.waypoints[i], self.waypoints[i + 1]);
+
+        // Bézier control points from the tangents, so the curve leaves/enters with them
+        let p1 = p0 * ( (1.0 / 3.0) * self.tangent(i, around(i), duration(i))).exp();
+        let p2 = p3 * (-(1.0 / 3.0) * self.tangent(i + 1, around(i + 1), duration(i))).exp();
+
+        bezier(&[p0, p1, p2, p3], alpha)
+    }
+
+    /// Evaluate the spline at `t` in [0, segments], where integer values hit the waypoints.
+    /// Values outside of this range are clamped. Panics if there are no waypoints.
+    pub fn evaluate(&self, t: Scalar) -> DualQuaternion
+    {
+        if self.segments() == 0 {
+            return self.waypoints[0]
+        }
+
+        let t = t.clamp(0.0, self.segments() as Scalar);
+        let i = (t.floor() as usize).min(self.segments() - 1);
+
+        self.evaluate_segment(i, t - i as Scalar)
+    }
+
+    /// Reparameterize this spline by arc length, so equal parameter steps give equal perceived speed.
+    /// The length of a step is `rotation_weight * angle + translation_weight * distance`, see `DualQuaternion::distance_to()`.
+    /// Every segment is approximated by `samples_per_segment` straight pieces.
+    pub fn by_arc_length(&self, rotation_weight: Scalar, translation_weight: Scalar, samples_per_segment: usize) -> ArcLengthSpline
+    {
+        let samples = (self.segments() * samples_per_segment.max(1)).max(1);
+        let step = self.segments() as Scalar / samples as Scalar;
+
+        let mut parameters = vec![0.0];
+        let mut lengths = vec![0.0];
+        let mut previous = self.evaluate(0.0);
+
+        for n in 1..=samples
+        {
+            let t = n as Scalar * step;
+            let current = self.evaluate(t);
+
+            let length = lengths[lengths.len() - 1] + previous.distance_to(&current, rotation_weight, translation_weight);
+
+            parameters.push(t);
+            lengths.push(length);
+            previous = current;
+        }
+
+        ArcLengthSpline { spline: self.clone(), parameters, lengths }
+    }
+}
+
+/// A spline parameterized by (weighted) arc length, see `MotorCatmullRom::by_arc_length()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthSpline
+{
+    spline: MotorCatmullRom,
+    /// Sampled spline parameters with the arc length up to them, both increasing
+    parameters: Vec<Scalar>,
+    lengths:    Vec<Scalar>,
+}
+
+impl ArcLengthSpline
+{
+    pub fn spline(&self) -> &MotorCatmullRom { &self.spline }
+
+    /// Total (weighted) length of the spline.
+    pub fn length(&self) -> Scalar { self.lengths[self.lengths.len() - 1] }
+
+    /// The spline parameter where the arc length `s` is reached. `s` is clamped to [0, length].
+    pub fn parameter_at(&self, s: Scalar) -> Scalar
+    {
+        let s = s.clamp(0.0, self.length());
+
+        // First sample reaching s, then linearly interpolate within the piece before it
+        let i = self.lengths.partition_point(|&length| length < s).clamp(1, self.lengths.len() - 1);
+
+        let (l0, l1) = (self.lengths[i - 1], self.lengths[i]);
+        let (t0, t1) = (self.parameters[i - 1], self.parameters[i]);
+
+        if (l1 - l0).abs() < Scalar::EPSILON {
+            return t0
+        }
+
+        t0 + (t1 - t0) * (s - l0) / (l1 - l0)
+    }
+
+    /// Evaluate the spline after the arc length `s`.
+    pub fn evaluate(&self, s: Scalar) -> DualQuaternion
+    {
+        self.spline.evaluate(self.parameter_at(s))
+    }
+
+    /// Evaluate the spline at `u` in [0,1], where 0 is the start and 1 the end.
+    pub fn evaluate_normalized(&self, u: Scalar) -> DualQuaternion
+    {
+        self.evaluate(u * self.length())
+    }
+}
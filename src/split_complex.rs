@@ -0,0 +1,174 @@
+//! Split-complex (or hyperbolic) numbers are like complex numbers, but instead of i^2 = -1 we define j^2 = +1.
+//! Where unit complex numbers are rotations, unit split-complex numbers are hyperbolic rotations,
+//! e.g. Lorentz boosts in special relativity.
+
+use crate::util::{Scalar, write_terms};
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, PartialOrd, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
+    derive_more::Neg, derive_more::From
+)]
+pub struct SplitComplex
+{
+    pub re: Scalar,
+    pub hy: Scalar,
+}
+
+impl From<Scalar> for SplitComplex
+{
+    fn from(value: Scalar) -> Self { SplitComplex { re: value, hy: 0.0 } }
+}
+
+impl From<&Scalar> for SplitComplex
+{
+    fn from(value: &Scalar) -> Self { SplitComplex { re: *value, hy: 0.0 } }
+}
+
+impl core::fmt::Display for SplitComplex
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_terms(f, &[(self.re, ""), (self.hy, "j")])
+    }
+}
+
+impl SplitComplex
+{
+    pub const ZERO: SplitComplex = SplitComplex { re: 0.0, hy: 0.0 };
+    pub const ONE:  SplitComplex = SplitComplex { re: 1.0, hy: 0.0 };
+    pub const HYP:  SplitComplex = SplitComplex { re: 0.0, hy: 1.0 };
+
+    pub fn new(re: Scalar, hy: Scalar) -> Self
+    {
+        SplitComplex { re, hy }
+    }
+
+    /// Hyperbolic rotation (= Lorentz boost) by the rapidity `rapidity`, i.e. exp(j*rapidity).
+    pub fn boost(rapidity: Scalar) -> Self
+    {
+        SplitComplex { re: rapidity.cosh(), hy: rapidity.sinh() }
+    }
+
+    /// The rapidity of this split-complex number, i.e. the hyperbolic angle.
+    /// Only meaningful if |.re| > |.hy|
+    pub fn rapidity(&self) -> Scalar
+    {
+        (self.hy / self.re).atanh()
+    }
+
+    /// Conjugate, i.e. negate the hyperbolic part
+    pub fn conj(&self) -> Self { Self { re: self.re, hy: -self.hy } }
+
+    /// The "natural" (squared) norm defined via conjugation (a*a.conj). It may be negative or zero for non-zero numbers.
+    pub fn modulus_squared(&self) -> Scalar { self.re*self.re - self.hy*self.hy }
+
+    /// Square root of the absolute value of `.modulus_squared()`
+    pub fn norm(&self) -> Scalar { self.modulus_squared().abs().sqrt() }
+
+    /// Normalize by `.norm()`.
+    /// May produce invalid numbers on the light cone, i.e. if |.re| = |.hy|
+    pub fn normalized(&self) -> Self { *self * (1.0 / self.norm()) }
+
+    /// Split-complex exponential function
+    pub fn exp(&self) -> Self
+    {
+        // exp(a+bj) = exp(a) * exp(bj) = exp(a) * (cosh(b) + j*sinh(b))
+
+        let r = self.re.exp();
+
+        Self {
+            re: r * self.hy.cosh(),
+            hy: r * self.hy.sinh()
+        }
+    }
+
+    /// Natural logarithm for split-complex numbers.
+    /// This may return invalid numbers if .re <= |.hy|
+    pub fn log(&self) -> Self
+    {
+        //    a+bj = r*exp(jx) with r = norm, x = rapidity
+        // => log(a+bj) = log(r) + jx
+
+        Self {
+            re: self.norm().ln(),
+            hy: self.rapidity()
+        }
+    }
+
+    /// Raise a split-complex number to some (real) power.
+    /// This may return invalid numbers if .re <= |.hy|
+    pub fn powf(&self, f: Scalar) -> Self
+    {
+        ( f * self.log() ).exp()
+    }
+
+    /// Linearily interpolate between this and `other`
+    pub fn lerp(&self, other: SplitComplex, alpha: Scalar) -> SplitComplex
+    {
+        (1.0 - alpha) * self + alpha * other
+    }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &SplitComplex, rhs: &SplitComplex| -> SplitComplex {
+    SplitComplex
+    {
+        re: lhs.re * rhs.re + lhs.hy * rhs.hy,
+        hy: lhs.hy * rhs.re + lhs.re * rhs.hy
+    }
+});
+auto_ops::impl_op_ex!(*= |lhs: &mut SplitComplex, rhs: &SplitComplex| { *lhs = *lhs * rhs });
+auto_ops::impl_op_ex_commutative!(* |lhs: &SplitComplex, rhs: &Scalar| -> SplitComplex {
+    SplitComplex
+    {
+        re: lhs.re * rhs,
+        hy: lhs.hy * rhs
+    }
+});
+auto_ops::impl_op_ex!(*= |lhs: &mut SplitComplex, rhs: &Scalar| {
+    lhs.re *= rhs;
+    lhs.hy *= rhs;
+});
+
+// Division is only possible if the modulus isn't zero, i.e. the divisor doesn't lie on the light cone
+auto_ops::impl_op_ex!(/ |lhs: &SplitComplex, rhs: &SplitComplex| -> SplitComplex { lhs * rhs.conj() * (1.0 / rhs.modulus_squared()) });
+auto_ops::impl_op_ex!(/= |lhs: &mut SplitComplex, rhs: &SplitComplex| { *lhs = *lhs / rhs });
+auto_ops::impl_op_ex!(/ |lhs: &SplitComplex, rhs: &Scalar| -> SplitComplex {
+    SplitComplex
+    {
+        re: lhs.re / rhs,
+        hy: lhs.hy / rhs
+    }
+});
+auto_ops::impl_op_ex!(/ |lhs: &Scalar, rhs: &SplitComplex| -> SplitComplex { lhs * rhs.conj() * (1.0 / rhs.modulus_squared()) });
+auto_ops::impl_op_ex!(/= |lhs: &mut SplitComplex, rhs: &Scalar| {
+    lhs.re /= rhs;
+    lhs.hy /= rhs;
+});
+
+auto_ops::impl_op_ex_commutative!(+ |lhs: &SplitComplex, rhs: &Scalar| -> SplitComplex {
+    SplitComplex
+    {
+        re: lhs.re + rhs,
+        hy: lhs.hy
+    }
+});
+auto_ops::impl_op_ex!(+= |lhs: &mut SplitComplex, rhs: &Scalar| { lhs.re += rhs });
+
+auto_ops::impl_op_ex!(- |lhs: &SplitComplex, rhs: &Scalar| -> SplitComplex {
+    SplitComplex
+    {
+        re: lhs.re - rhs,
+        hy: lhs.hy
+    }
+});
+auto_ops::impl_op_ex!(- |lhs: &Scalar, rhs: &SplitComplex| -> SplitComplex {
+    SplitComplex
+    {
+        re: lhs - rhs.re,
+        hy: -rhs.hy
+    }
+});
+auto_ops::impl_op_ex!(-= |lhs: &mut SplitComplex, rhs: &Scalar| { lhs.re -= rhs });
@@ -1,3 +1,4 @@
+#![allow(dead_code)]
 
 
 fn main()
@@ -6,7 +7,8 @@ fn main()
     //test_dual_quat_exp_brute_force();
     //test_dual_quat_log();
     //test_dual_quat_motor();
-    test_dual_quat_sclerp();
+    //test_dual_quat_sclerp();
+    test_dual_quat_root();
 }
 
 
@@ -52,6 +54,24 @@ fn test_dual_quat_motor()
     println!("{:?}", (screw2*screw1).transform_point(&[0.0,0.0,0.0]));
 }
 
+/// Applying the n-th root n times should give back the original motor
+fn test_dual_quat_root()
+{
+    use blanko_quaternions::dual_quaternion::*;
+
+    let line = DualQuaternion::line(&[0.0,1.0,0.0], &[1.0,0.0,0.0]);
+    let screw = DualQuaternion::screw(&line, Angle::degrees(120.0), 2.0);
+    let translator = DualQuaternion::translator(&[1.0, 2.0, 3.0]);
+
+    for motor in [screw, translator]
+    {
+        let root = motor.root(4);
+
+        println!("{:?}", root*root*root*root);
+        println!("{:?}", motor);
+    }
+}
+
 
 /// Check whether or not the logarithm behaves correctly
 fn test_dual_quat_log()
@@ -86,7 +106,7 @@ fn test_dual_quat_exp_brute_force()
             nth_power = nth_power * dq;
             fac *= i;
 
-            let s = nth_power * (1.0 / (fac as f32));
+            let s = nth_power * (1.0 / (fac as blanko_quaternions::Scalar));
 
             result += s;
         }
@@ -104,3 +124,417 @@ fn test_dual_quat_exp_brute_force()
         println!("r: {}\ni: {}\n", r_margin, i_margin);
     }
 }
+
+
+/// Angles given in round degrees should give them back exactly, also after rounding to multiples
+#[test]
+fn test_angle_exact_degrees()
+{
+    use blanko_quaternions::angle::Angle;
+
+    assert_eq!(Angle::degrees(60.0).deg(), 60.0);
+    assert_eq!(Angle::degrees(22.5).deg(), 22.5);
+    assert_eq!(Angle::degrees(-135.0).deg(), -135.0);
+
+    assert_eq!(Angle::degrees(90.0).ceil(Angle::degrees(10.0)).deg(), 90.0);
+    assert_eq!(Angle::degrees(60.0).floor(Angle::degrees(60.0)).deg(), 60.0);
+    assert_eq!(Angle::degrees(95.0).round(Angle::degrees(10.0)).deg(), 100.0);
+    assert_eq!(Angle::degrees(-95.0).ceil(Angle::degrees(10.0)).deg(), -100.0);
+    assert_eq!((Angle::degrees(100.0) % Angle::degrees(30.0)).deg(), 10.0);
+}
+
+
+/// Converting Euler angles in gimbal lock back and forth should keep the rotation
+#[test]
+fn test_euler_gimbal_lock()
+{
+    use blanko_quaternions::euler::*;
+
+    let euler = EulerAngles::new(Angle::degrees(30.0), Angle::degrees(90.0), Angle::degrees(20.0), EulerOrder::XYZ);
+    let q = euler.to_quaternion();
+    let r = EulerAngles::from_quaternion(&q, EulerOrder::XYZ).to_quaternion();
+
+    let dot = q.w*r.w + q.i*r.i + q.j*r.j + q.k*r.k;
+    assert!(dot.abs() > 0.99999, "{dot}");
+}
+
+
+/// Screws built from a Line should not depend on the length of its direction
+#[test]
+fn test_dual_quat_from_screw()
+{
+    use blanko_quaternions::dual_quaternion::*;
+
+    // Line through (1,0,0) in direction (0,0,2), moment = p X dir
+    let line = Line { dir: [0.0, 0.0, 2.0], moment: [0.0, -2.0, 0.0] };
+    let screw = DualQuaternion::from_screw(&line, Angle::degrees(90.0), 3.0);
+
+    let p = screw.transform_point(&[0.0, 0.0, 0.0]);
+    let expected = [1.0, -1.0, 3.0];
+
+    for (a, b) in p.iter().zip(expected)
+    {
+        assert!((a - b).abs() < 1e-5, "{p:?}");
+    }
+
+    assert_eq!(screw, DualQuaternion::screw(&line.into(), Angle::degrees(90.0), 3.0));
+}
+
+
+/// The logarithm of a tiny rotation should keep its rotational part
+#[test]
+fn test_dual_quat_log_small_rotation()
+{
+    use blanko_quaternions::dual_quaternion::*;
+
+    let rotor = DualQuaternion::rotor(Angle::degrees(0.02), &[0.0, 0.0, 1.0]);
+    let motor = DualQuaternion::translator(&[1.0, 2.0, 3.0]) * rotor;
+
+    let log = motor.log();
+    assert!((log.k - 0.5 * Angle::degrees(0.02).rad()).abs() < 1e-9, "{log:?}");
+
+    let exp = log.exp();
+    assert!((exp - motor).norm() + (exp - motor).inorm() < 1e-5, "{exp:?}");
+}
+
+
+/// Vector3 converts from and into arrays and tuples
+#[test]
+fn test_vector3_conversions()
+{
+    use blanko_quaternions::vector3::Vector3;
+
+    let v = Vector3::from((1.0, 2.0, 3.0));
+    assert_eq!(v, Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+    assert_eq!(v, Vector3::from([1.0, 2.0, 3.0]));
+    assert_eq!(<(_, _, _)>::from(v), (1.0, 2.0, 3.0));
+    assert_eq!(<[_; 3]>::from(v), [1.0, 2.0, 3.0]);
+}
+
+
+/// Small but regular matrices are invertible, singular ones aren't
+#[test]
+fn test_matrix3_inverse_scale()
+{
+    use blanko_quaternions::matrix::Matrix3;
+
+    let inverse = (Matrix3::IDENTITY * 0.001).inverse().unwrap();
+    assert!((inverse.get(1, 1) - 1000.0).abs() < 1e-2, "{inverse:?}");
+
+    let singular = Matrix3 { columns: [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 0.0, 1.0]] };
+    assert!(singular.inverse().is_none());
+}
+
+
+/// A spline track through unevenly spaced keyframes of a uniform motion should keep the motion uniform
+#[test]
+fn test_pose_track_spline_timing()
+{
+    use blanko_quaternions::animation::*;
+
+    let mut track = PoseTrack::new(Interpolation::Spline, LoopMode::Clamp);
+
+    for time in [0.0, 1.0, 3.0, 3.5]
+    {
+        track.insert(time, DualQuaternion::translator(&[time, 0.0, 0.0]));
+    }
+
+    // The end points have one-sided tangents, so only the inner segment is checked
+    for time in [1.2, 2.0, 2.5, 2.9]
+    {
+        let x = track.sample(time).unwrap().translation()[0];
+        assert!((x - time).abs() < 1e-4, "{time}: {x}");
+    }
+}
+
+
+/// All forms of the vector macros give the same vector
+#[test]
+fn test_vector_macros()
+{
+    use blanko_quaternions::{vector2, vector3};
+    use blanko_quaternions::vector2::Vector2;
+    use blanko_quaternions::vector3::Vector3;
+
+    const V: Vector2 = vector2!(1.0, 2.0);
+
+    assert_eq!(V, Vector2::new(1.0, 2.0));
+    assert_eq!(vector2!([1.0, 2.0]), V);
+    assert_eq!(vector2!((1.0, 2.0)), V);
+    assert_eq!(vector2!(0.5), Vector2::new(0.5, 0.5));
+
+    assert_eq!(vector3!((1.0, 2.0, 3.0)), Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+}
+
+
+/// Full negative turns wrap to 0°, not 360°
+#[test]
+fn test_angle_corrected_full_turns()
+{
+    use blanko_quaternions::angle::Angle;
+
+    assert_eq!(Angle::degrees(-360.0).corrected().rad(), 0.0);
+    assert_eq!(Angle::degrees(-720.0).corrected().rad(), 0.0);
+    assert_eq!(Angle::radians(-1e-3 * blanko_quaternions::Scalar::EPSILON).corrected().rad(), 0.0);
+    assert!((Angle::degrees(-20.0).corrected().deg() - 340.0).abs() < 1e-3);
+    assert_eq!(Angle::degrees(-360.0).wrapped_symmetric().rad(), 0.0);
+}
+
+
+/// NaN angles are greater than all others, whatever their sign
+#[test]
+fn test_angle_total_cmp_nan()
+{
+    use blanko_quaternions::angle::Angle;
+    use core::cmp::Ordering;
+
+    let nan = Angle::radians(blanko_quaternions::Scalar::NAN);
+    let neg_nan = Angle::radians(-blanko_quaternions::Scalar::NAN);
+    let angle = Angle::degrees(350.0);
+
+    assert_eq!(nan.total_cmp(&angle), Ordering::Greater);
+    assert_eq!(neg_nan.total_cmp(&angle), Ordering::Greater);
+    assert_eq!(neg_nan.total_cmp(&nan), Ordering::Equal);
+}
+
+
+/// Ranges include their end within rounding errors and sweep full turns
+#[test]
+fn test_angle_range_to()
+{
+    use blanko_quaternions::angle::Angle;
+
+    use blanko_quaternions::Scalar;
+
+    let degrees = |start: Scalar, end: Scalar, step: Scalar| -> Vec<Scalar> {
+        Angle::degrees(start).range_to(Angle::degrees(end), Angle::degrees(step)).map(|angle| angle.deg().round()).collect()
+    };
+
+    assert_eq!(degrees(0.0, 360.0, 90.0), [0.0, 90.0, 180.0, 270.0, 360.0]);
+    assert_eq!(degrees(0.0, -360.0, -90.0), [0.0, -90.0, -180.0, -270.0, -360.0]);
+    assert_eq!(degrees(350.0, 10.0, 5.0).len(), 5);
+    assert_eq!(degrees(0.0, 0.3, 0.1).len(), 4);
+    assert_eq!(degrees(10.0, 10.0, 5.0), [10.0]);
+}
+
+
+/// Slow rotations, e.g. of the earth, shouldn't get lost when integrated in small steps
+#[test]
+fn test_angular_velocity_slow_rotation()
+{
+    use blanko_quaternions::angular_velocity::*;
+    use core::time::Duration;
+
+    let earth = AngularVelocity::from_axis(&[0.0, 0.0, 1.0], Angle::radians(7.29e-5));
+    let mut q = Quaternion::ONE;
+
+    for _ in 0..3600
+    {
+        q += earth * Duration::from_secs(1);
+    }
+
+    let angle = AxisAngle::from_quaternion(&q).angle.deg();
+    assert!((angle - 15.04).abs() < 0.05, "{angle}");
+}
+
+
+/// Small rotations keep their axis and angle
+#[test]
+fn test_axis_angle_small_rotation()
+{
+    use blanko_quaternions::axis_angle::*;
+
+    let q = Quaternion::rotor(Angle::degrees(0.03), &[0.0, 0.0, 1.0]);
+    let aa = AxisAngle::from_quaternion(&q);
+
+    assert!((aa.angle.deg() - 0.03).abs() < 1e-5, "{aa:?}");
+    assert_eq!(aa.axis, [0.0, 0.0, 1.0]);
+
+    let twice = aa * aa;
+    assert!((twice.angle.deg() - 0.06).abs() < 1e-5, "{twice:?}");
+
+    assert_eq!(AxisAngle::from_quaternion(&Quaternion::ONE), AxisAngle::IDENTITY);
+}
+
+
+/// Slerp between close rotations should still move
+#[test]
+fn test_quat_slerp_close_rotations()
+{
+    use blanko_quaternions::axis_angle::*;
+
+    let a = Quaternion::rotor(Angle::degrees(10.0), &[0.0, 0.0, 1.0]);
+    let b = Quaternion::rotor(Angle::degrees(10.02), &[0.0, 0.0, 1.0]);
+
+    for (alpha, expected) in [(0.0, 10.0), (0.5, 10.01), (1.0, 10.02)]
+    {
+        let angle = AxisAngle::from_quaternion(&a.slerp(b, alpha)).angle.deg();
+        assert!((angle - expected).abs() < 1e-3, "{alpha}: {angle}");
+    }
+
+    let log = Quaternion::rotor(Angle::degrees(0.01), &[1.0, 0.0, 0.0]).log();
+    assert!((log.i - 0.5 * Angle::degrees(0.01).rad()).abs() < 1e-9, "{log:?}");
+    assert_eq!(Quaternion { w: 2.0, i: 0.0, j: 0.0, k: 0.0 }.log().i, 0.0);
+}
+
+
+/// Renormalizing makes a perturbed motor rigid again
+#[test]
+fn test_dual_quat_renormalized()
+{
+    use blanko_quaternions::dual_quaternion::*;
+
+    let motor = DualQuaternion::translator(&[1.0, 2.0, 3.0]) * DualQuaternion::rotor(Angle::degrees(40.0), &[1.0, 1.0, 0.0]);
+    let perturbed = DualQuaternion { w: motor.w * 1.01, we: motor.we + 0.05, ..motor };
+
+    let q = perturbed.renormalized();
+
+    assert!((q.norm() - 1.0).abs() < 1e-6);
+    assert!((q.w*q.we + q.i*q.ie + q.j*q.je + q.k*q.ke).abs() < 1e-6);
+}
+
+
+/// Degenerate planes are rejected, independent of the units
+#[test]
+fn test_plane_degenerate()
+{
+    use blanko_quaternions::plane::Plane;
+    use blanko_quaternions::line::Line;
+
+    assert!(Plane::from_three_points(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], &[2.0, 2.0, 2.0]).is_none());
+    assert!(Plane::from_three_points(&[0.0, 0.0, 0.0], &[1e-3, 0.0, 0.0], &[0.0, 1e-3, 0.0]).is_some());
+
+    let line = Line::new(&[0.0, 0.0, 0.0], &[1.0, 0.0, 0.0]);
+    assert!(Plane::from_line_and_point(&line, &[5.0, 0.0, 0.0]).is_none());
+
+    let plane = Plane::from_line_and_point(&line, &[1.0, 3e-4, 0.0]).unwrap();
+    assert!(plane.normal[2].abs() > 0.999, "{plane:?}");
+}
+
+
+/// Wahba's problem: the rotor is recovered from rotated directions, and the inverse one for swapped pairs
+#[test]
+fn test_quat_from_direction_pairs()
+{
+    use blanko_quaternions::quaternion::*;
+
+    let rotor = Quaternion::rotor(Angle::degrees(70.0), &[1.0, -2.0, 0.5]);
+    let directions = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.3, -0.4, 2.0], [-1.0, 0.5, 0.2]];
+
+    let pairs: Vec<_> = directions.iter().map(|d| (*d, rotor.transform_vector(d))).collect();
+    let swapped: Vec<_> = pairs.iter().map(|(a, b)| (*b, *a)).collect();
+    let weights = [1.0, 2.0, 0.5, 1.0];
+
+    let same_rotation = |a: Quaternion, b: Quaternion| (a.w*b.w + a.i*b.i + a.j*b.j + a.k*b.k).abs() > 0.99999;
+
+    let found = Quaternion::from_direction_pairs(&pairs, &weights);
+    assert!(same_rotation(found, rotor), "{found:?}");
+
+    let found = Quaternion::from_direction_pairs(&swapped, &weights);
+    assert!(same_rotation(found, rotor.conj()), "{found:?}");
+}
+
+
+/// CCD and FABRIK reach reachable targets and stretch towards unreachable ones
+#[test]
+fn test_ik_solvers()
+{
+    use blanko_quaternions::ik::*;
+
+    let chain = || {
+        let mut chain = KinematicChain::new(DualQuaternion::ONE);
+        chain.push(Joint::new(DualQuaternion::ONE));
+        chain.push(Joint::new(DualQuaternion::translator(&[1.0, 0.0, 0.0])));
+        chain.tool = DualQuaternion::translator(&[1.0, 0.0, 0.0]);
+        chain
+    };
+
+    let solver = IkSolver::new(64, 1e-3);
+
+    for solve in [IkSolver::ccd, IkSolver::fabrik]
+    {
+        let mut reachable = chain();
+        let result = solve(&solver, &mut reachable, &[0.5, 1.2, 0.3]);
+        assert!(result.converged, "{result:?}");
+
+        let p = reachable.end_effector().translation();
+        assert!(((p[0] - 0.5).powi(2) + (p[1] - 1.2).powi(2) + (p[2] - 0.3).powi(2)).sqrt() <= 1e-3, "{p:?}");
+
+        let mut unreachable = chain();
+        let result = solve(&solver, &mut unreachable, &[0.0, 5.0, 0.0]);
+        assert!(!result.converged && (result.error - 3.0).abs() < 1e-2, "{result:?}");
+    }
+}
+
+
+/// Both filters converge to the true orientation of a resting sensor, Mahony also estimates the gyroscope bias
+#[test]
+fn test_fusion_filters()
+{
+    use blanko_quaternions::fusion::*;
+    use blanko_quaternions::angle::Angle;
+
+    let truth = Quaternion::rotor(Angle::degrees(60.0), &[1.0, 2.0, 3.0]);
+
+    // Gravity ("up") and earth's magnetic field (north and down) in the sensor frame
+    let accel = truth.conj().transform_vector(&[0.0, 0.0, 1.0]);
+    let mag = truth.conj().transform_vector(&[0.6, 0.0, -0.8]);
+    let bias = [0.01, -0.02, 0.005];
+
+    let close_to_truth = |q: Quaternion| (q.w*truth.w + q.i*truth.i + q.j*truth.j + q.k*truth.k).abs() > 0.9999;
+
+    let mut madgwick = Madgwick::new(0.5);
+    let mut mahony = Mahony::new(2.0, 0.5);
+
+    for _ in 0..5000
+    {
+        madgwick.update(&[0.0; 3], &accel, &mag, 0.01);
+        mahony.update(&bias, &accel, &mag, 0.01);
+    }
+
+    assert!(close_to_truth(madgwick.orientation), "{:?}", madgwick.orientation);
+    assert!(close_to_truth(mahony.orientation), "{:?}", mahony.orientation);
+
+    for (correction, bias) in mahony.bias_correction().iter().zip(bias)
+    {
+        assert!((correction + bias).abs() < 1e-3, "{:?}", mahony.bias_correction());
+    }
+}
+
+
+/// Boxes are separated by face normals and by cross products of their edges
+#[test]
+fn test_obb_overlaps()
+{
+    use blanko_quaternions::obb::*;
+    use blanko_quaternions::angle::Angle;
+
+    let cube = |center: [blanko_quaternions::Scalar; 3], orientation: Quaternion| Obb::new(&center, &[1.0, 1.0, 1.0], orientation);
+
+    let a = cube([0.0; 3], Quaternion::ONE);
+    assert!(a.overlaps(&cube([1.5, 0.0, 0.0], Quaternion::ONE)));
+    assert!(!a.overlaps(&cube([2.5, 0.0, 0.0], Quaternion::ONE)));
+    assert!(a.contains_point(&[0.9, -0.9, 0.5]) && !a.contains_point(&[1.1, 0.0, 0.0]));
+
+    // An edge parallel to z meets an edge parallel to y, only their cross product (the x-axis) separates them
+    let a = cube([0.0; 3], Quaternion::rotor(Angle::degrees(45.0), &[0.0, 0.0, 1.0]));
+    let b = |x| cube([x, 0.0, 0.0], Quaternion::rotor(Angle::degrees(45.0), &[0.0, 1.0, 0.0]));
+
+    assert!(a.overlaps(&b(2.7)));
+    assert!(!a.overlaps(&b(2.9)));
+}
+
+
+/// Unknown units are reported without eating letters of the unit
+#[test]
+fn test_angle_parse_unknown_unit()
+{
+    use blanko_quaternions::angle::*;
+
+    let unknown = |s: &str| ParseAngleError::UnknownUnit(s.to_string());
+
+    assert_eq!("eval".parse::<Angle>(), Err(unknown("eval")));
+    assert_eq!("1.5e3 grad".parse::<Angle>(), Err(unknown("grad")));
+    assert_eq!("12em".parse::<Angle>(), Err(unknown("em")));
+    assert_eq!("90 deg".parse::<Angle>(), Ok(Angle::degrees(90.0)));
+}
@@ -0,0 +1,108 @@
+//! A rigid transformation which remembers values derived from its motor (matrix, inverse, ...),
+//! so they don't need to be recomputed every time, e.g. every frame in a render loop.
+
+pub use crate::dual_quaternion::{DualQuaternion, Quaternion};
+
+use crate::util::Scalar;
+use core::cell::OnceCell;
+
+/// Wrapper around a normalized DualQuaternion caching its derived data.
+/// The caches are computed when first needed and cleared whenever the motor changes.
+///
+/// Since the caches are filled through `&self`, a Transform is `Send` but not `Sync`,
+/// so it can't be shared by reference between threads. Clone it (or share its motor) instead.
+#[derive(Debug, Clone)]
+pub struct Transform
+{
+    motor: DualQuaternion,
+
+    matrix:      OnceCell<[[Scalar; 4]; 4]>,
+    inverse:     OnceCell<DualQuaternion>,
+    translation: OnceCell<[Scalar; 3]>,
+}
+
+impl Default for Transform
+{
+    fn default() -> Self { Transform::new(DualQuaternion::ONE) }
+}
+
+impl From<DualQuaternion> for Transform
+{
+    fn from(motor: DualQuaternion) -> Self { Transform::new(motor) }
+}
+
+impl Transform
+{
+    pub fn new(motor: DualQuaternion) -> Self
+    {
+        Transform {
+            motor,
+            matrix:      OnceCell::new(),
+            inverse:     OnceCell::new(),
+            translation: OnceCell::new(),
+        }
+    }
+
+    /// The wrapped motor.
+    pub fn motor(&self) -> &DualQuaternion
+    {
+        &self.motor
+    }
+
+    /// Replace the wrapped motor.
+    pub fn set_motor(&mut self, motor: DualQuaternion)
+    {
+        *self = Transform::new(motor);
+    }
+
+    /// Change the wrapped motor in place.
+    pub fn update(&mut self, f: impl FnOnce(&mut DualQuaternion))
+    {
+        let mut motor = self.motor;
+        f(&mut motor);
+
+        self.set_motor(motor);
+    }
+
+    /// Column-major 4x4 matrix, see `DualQuaternion::to_matrix4()`.
+    pub fn matrix(&self) -> &[[Scalar; 4]; 4]
+    {
+        self.matrix.get_or_init(|| self.motor.to_matrix4())
+    }
+
+    /// The inverse motor.
+    pub fn inverse(&self) -> &DualQuaternion
+    {
+        self.inverse.get_or_init(|| self.motor.inverse())
+    }
+
+    /// The rotational part of the motor.
+    pub fn rotation(&self) -> Quaternion
+    {
+        self.motor.rotation()
+    }
+
+    /// The translational part of the motor, see `DualQuaternion::translation()`.
+    pub fn translation(&self) -> &[Scalar; 3]
+    {
+        self.translation.get_or_init(|| self.motor.translation())
+    }
+
+    /// Transform a 3D-vector as point using the cached matrix.
+    pub fn transform_point(&self, point: &[Scalar]) -> [Scalar; 3]
+    {
+        let m = self.matrix();
+
+        core::array::from_fn(|row| m[0][row] * point[0] + m[1][row] * point[1] + m[2][row] * point[2] + m[3][row])
+    }
+
+    /// Transform a 3D-vector as direction using the cached matrix, i.e. it won't be translated.
+    pub fn transform_vector3(&self, vector: &[Scalar]) -> [Scalar; 3]
+    {
+        let m = self.matrix();
+
+        core::array::from_fn(|row| m[0][row] * vector[0] + m[1][row] * vector[1] + m[2][row] * vector[2])
+    }
+}
+
+auto_ops::impl_op_ex!(* |lhs: &Transform, rhs: &Transform| -> Transform { Transform::new(lhs.motor * rhs.motor) });
@@ -1,3 +1,5 @@
+//! The scalar type used for all components, see the `use_f64` feature.
+
 
 
 /// Used instead of f32/f64 to avoid unnecessary generics
@@ -7,3 +9,117 @@ pub type Scalar = f32;
 /// Used instead of f32/f64 to avoid unnecessary generics
 #[cfg(feature = "use_f64")]
 pub type Scalar = f64;
+
+/// Write `terms` (value and unit) as a sum like `3 - 4i + 2j`, leaving out (nearly) zero terms.
+/// Writes `0` if all terms are zero. A precision of `f` (e.g. `{:.2}`) is applied to all values.
+pub(crate) fn write_terms(f: &mut core::fmt::Formatter<'_>, terms: &[(Scalar, &str)]) -> core::fmt::Result
+{
+    let mut empty = true;
+
+    for &(value, unit) in terms
+    {
+        if value * value <= Scalar::EPSILON {
+            continue
+        }
+
+        // The sign of all but the first term becomes the operator
+        let value = if empty { value } else {
+            write!(f, "{}", if value < 0.0 { " - " } else { " + " })?;
+            value.abs()
+        };
+
+        match f.precision()
+        {
+            Some(precision) => write!(f, "{:.*}{}", precision, value, unit)?,
+            None => write!(f, "{}{}", value, unit)?,
+        }
+
+        empty = false;
+    }
+
+    if empty {
+        write!(f, "0")?;
+    }
+
+    Ok(())
+}
+
+/// The float functions which are only inherent methods with `std`, routed through `libm` otherwise.
+/// Modules using them import this with `#[cfg(not(feature = "std"))]`, so the call sites stay the same.
+#[cfg(not(feature = "std"))]
+pub(crate) trait Float: Sized
+{
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn tanh(self) -> Self;
+    fn asinh(self) -> Self;
+    fn acosh(self) -> Self;
+    fn atanh(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn div_euclid(self, rhs: Self) -> Self;
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! impl_float
+{
+    ($t:ty, $atan2:ident, $hypot:ident, $pow:ident, $sincos:ident; $($name:ident => $libm:ident),*) => {
+        impl Float for $t
+        {
+            $( fn $name(self) -> Self { libm::$libm(self) } )*
+
+            fn atan2(self, x: Self) -> Self { libm::$atan2(self, x) }
+            fn hypot(self, other: Self) -> Self { libm::$hypot(self, other) }
+            fn powf(self, n: Self) -> Self { libm::$pow(self, n) }
+            fn powi(self, n: i32) -> Self { libm::$pow(self, n as $t) }
+            fn sin_cos(self) -> (Self, Self) { libm::$sincos(self) }
+
+            // Same as std
+            fn rem_euclid(self, rhs: Self) -> Self
+            {
+                let r = self % rhs;
+                if r < 0.0 { r + rhs.abs() } else { r }
+            }
+
+            fn div_euclid(self, rhs: Self) -> Self
+            {
+                let q = (self / rhs).trunc();
+                if self % rhs < 0.0 {
+                    return if rhs > 0.0 { q - 1.0 } else { q + 1.0 }
+                }
+                q
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "std"))]
+impl_float!(f32, atan2f, hypotf, powf, sincosf;
+    sqrt => sqrtf, exp => expf, ln => logf, sin => sinf, cos => cosf, tan => tanf,
+    asin => asinf, acos => acosf, atan => atanf, sinh => sinhf, cosh => coshf, tanh => tanhf,
+    asinh => asinhf, acosh => acoshf, atanh => atanhf, floor => floorf, ceil => ceilf, round => roundf, trunc => truncf
+);
+
+#[cfg(not(feature = "std"))]
+impl_float!(f64, atan2, hypot, pow, sincos;
+    sqrt => sqrt, exp => exp, ln => log, sin => sin, cos => cos, tan => tan,
+    asin => asin, acos => acos, atan => atan, sinh => sinh, cosh => cosh, tanh => tanh,
+    asinh => asinh, acosh => acosh, atanh => atanh, floor => floor, ceil => ceil, round => round, trunc => trunc
+);
@@ -0,0 +1,79 @@
+//! 2D vectors for points and directions in the plane, so 2D data doesn't need to be
+//! smuggled through 3D vectors with z = 0.
+
+use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
+
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable,
+    derive_more::Add, derive_more::AddAssign, derive_more::Sub, derive_more::SubAssign,
+    derive_more::Neg, derive_more::From
+)]
+pub struct Vector2
+{
+    pub x: Scalar,
+    pub y: Scalar
+}
+
+impl Vector2
+{
+    pub const ZERO: Self = Self { x: 0.0, y: 0.0 };
+    pub const X:    Self = Self { x: 1.0, y: 0.0 };
+    pub const Y:    Self = Self { x: 0.0, y: 1.0 };
+
+    pub fn new(x: Scalar, y: Scalar) -> Self
+    {
+        Self { x, y }
+    }
+
+    pub fn norm(&self) -> Scalar
+    {
+        (self.x*self.x + self.y*self.y).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self
+    {
+        self * (1.0/self.norm())
+    }
+
+    pub fn dot(&self, other: &Self) -> Scalar
+    {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z-component of the 3D cross product, i.e. the signed area of the parallelogram
+    /// spanned by both vectors. Positive if `other` is counter-clockwise from `self`.
+    pub fn cross(&self, other: &Self) -> Scalar
+    {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Linearily interpolate between `self` and `other`
+    pub fn lerp(&self, other: &Self, alpha: Scalar) -> Self
+    {
+        (1.0 - alpha) * self + alpha * other
+    }
+}
+
+auto_ops::impl_op_ex_commutative!(* |lhs: &Vector2, rhs: &Scalar| -> Vector2 {
+    Vector2 {
+        x: lhs.x * rhs,
+        y: lhs.y * rhs
+    }
+});
+
+impl From<Vector2> for [Scalar;2]
+{
+    fn from(value: Vector2) -> Self {
+        [value.x, value.y]
+    }
+}
+
+impl From<[Scalar;2]> for Vector2
+{
+    fn from(value: [Scalar;2]) -> Self {
+        Vector2 { x: value[0], y: value[1] }
+    }
+}
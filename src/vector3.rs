@@ -1,7 +1,13 @@
+//! 3D vectors for points and directions. Most of this crate takes and returns plain `[Scalar; 3]` arrays
+//! to stay compatible with other, probably better, lin-alg crates, and they convert from and into `Vector3`.
+
+use crate::angle::Angle;
 use crate::util::Scalar;
+#[cfg(not(feature = "std"))]
+use crate::util::Float;
 
-/// Used internally for easier manipulation of vectors.
-/// Internally only to keep this library simple and compatible with other, probably better, lin-alg crates
+/// A 3D vector. It converts from and into `[Scalar; 3]` and `(Scalar, Scalar, Scalar)`,
+/// the tuple conversion from `(x, y, z)` comes from `derive_more::From`.
 #[repr(C)]
 #[derive(
     Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable,
@@ -17,16 +23,286 @@ pub struct Vector3
 
 impl Vector3
 {
+    pub const ZERO:   Self = Self { x: 0.0, y: 0.0, z: 0.0 };
+    /// Same as `ZERO`, for points.
+    pub const ORIGIN: Self = Self::ZERO;
+    pub const X:      Self = Self { x: 1.0, y: 0.0, z: 0.0 };
+    pub const Y:      Self = Self { x: 0.0, y: 1.0, z: 0.0 };
+    pub const Z:      Self = Self { x: 0.0, y: 0.0, z: 1.0 };
+    pub const NEG_X:  Self = Self { x: -1.0, y: 0.0, z: 0.0 };
+    pub const NEG_Y:  Self = Self { x: 0.0, y: -1.0, z: 0.0 };
+    pub const NEG_Z:  Self = Self { x: 0.0, y: 0.0, z: -1.0 };
+
+    pub fn new(x: Scalar, y: Scalar, z: Scalar) -> Self
+    {
+        Self { x, y, z }
+    }
+
+    /// Squared length, cheaper than `norm()` e.g. for comparing lengths.
+    pub fn norm_squared(&self) -> Scalar
+    {
+        self.dot(self)
+    }
+
     pub fn norm(&self) -> Scalar
     {
         ( self.x*self.x + self.y*self.y + self.z*self.z).sqrt()
     }
 
+    /// Normalized copy of this vector, or `None` if it's (nearly) zero or not finite, where `normalize()` would give NaNs.
+    pub fn try_normalize(&self) -> Option<Self>
+    {
+        let norm = self.norm();
+
+        (norm > Scalar::EPSILON && norm.is_finite()).then(|| self * (1.0 / norm))
+    }
+
+    /// Whether all components are neither infinite nor NaN.
+    pub fn is_finite(&self) -> bool
+    {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Whether the length differs from 1 by at most `eps`.
+    pub fn is_normalized(&self, eps: Scalar) -> bool
+    {
+        (self.norm() - 1.0).abs() <= eps
+    }
+
+    /// Linearily interpolate between `self` and `other`
+    pub fn lerp(&self, other: &Self, alpha: Scalar) -> Self
+    {
+        (1.0 - alpha) * self + alpha * other
+    }
+
+    /// Interpolate between the unit vectors `self` and `other` along the great circle,
+    /// i.e. with constant angular speed. Nearly equal vectors are interpolated linearily and normalized instead.
+    /// For opposite vectors any of the great circles connecting them is taken.
+    pub fn slerp(&self, other: &Self, alpha: Scalar) -> Self
+    {
+        let angle = self.angle_between(other).rad();
+
+        if angle < 1e-3 {
+            return self.lerp(other, alpha).normalize()
+        }
+
+        // Unit vector perpendicular to self in the plane of the great circle
+        let towards = other.reject_from(self).try_normalize().unwrap_or_else(|| self.any_perpendicular());
+
+        let (sin, cos) = (alpha * angle).sin_cos();
+        cos * self + sin * towards
+    }
+
+    /// The point half-way between the points `self` and `other`.
+    pub fn midpoint(&self, other: &Self) -> Self
+    {
+        0.5 * (*self + *other)
+    }
+
+    /// Average of `points`, or `None` if there are none.
+    pub fn centroid(points: &[Self]) -> Option<Self>
+    {
+        if points.is_empty() {
+            return None
+        }
+
+        let sum = points.iter().fold(Self::ZERO, |acc, p| acc + *p);
+        Some(sum / points.len() as Scalar)
+    }
+
+    /// Weighted average of `points`, e.g. a center of mass. Returns `None` if the weights sum up to zero.
+    /// Points without a weight are ignored.
+    pub fn weighted_centroid(points: &[Self], weights: &[Scalar]) -> Option<Self>
+    {
+        let (sum, total) = points.iter().zip(weights)
+            .fold((Self::ZERO, 0.0), |(sum, total), (p, w)| (sum + *w * p, total + w));
+
+        (total != 0.0).then(|| sum / total)
+    }
+
+    /// Barycentric coordinates `(u, v, w)` of the point `self` in the triangle `a`, `b`, `c`,
+    /// so `self` is `u*a + v*b + w*c` after projecting it onto the triangle's plane.
+    /// All coordinates are in [0,1] iff the point lies inside the triangle. Returns `None` for degenerate triangles.
+    pub fn barycentric_in_triangle(&self, a: &Self, b: &Self, c: &Self) -> Option<(Scalar, Scalar, Scalar)>
+    {
+        // Solve self - a = v*(b - a) + w*(c - a) in the least squares sense
+        let (ab, ac, ap) = (*b - *a, *c - *a, *self - *a);
+        let (d00, d01, d11) = (ab.norm_squared(), ab.dot(&ac), ac.norm_squared());
+        let (d20, d21) = (ap.dot(&ab), ap.dot(&ac));
+
+        let denom = d00 * d11 - d01 * d01;
+
+        if denom <= Scalar::EPSILON * d00 * d11 {
+            return None
+        }
+
+        let v = (d11 * d20 - d01 * d21) / denom;
+        let w = (d00 * d21 - d01 * d20) / denom;
+
+        Some((1.0 - v - w, v, w))
+    }
+
+    /// The point with barycentric coordinates `(u, v, w)` in the triangle `a`, `b`, `c`, i.e. `u*a + v*b + w*c`.
+    pub fn from_barycentric(a: &Self, b: &Self, c: &Self, (u, v, w): (Scalar, Scalar, Scalar)) -> Self
+    {
+        u * a + v * b + w * c
+    }
+
+    /// Some unit vector perpendicular to this one, which mustn't be zero.
+    fn any_perpendicular(&self) -> Self
+    {
+        let helper = if self.x.abs() < 0.9 * self.norm() { Self::X } else { Self::Y };
+        self.cross(&helper).normalize()
+    }
+
+    /// Random unit vector, uniformly distributed on the sphere.
+    #[cfg(feature = "rand")]
+    pub fn random_unit<R: rand::Rng + ?Sized>(rng: &mut R) -> Self
+    {
+        // Archimedes: the z-coordinate of uniform points on the sphere is uniform
+        let z: Scalar = 2.0 * rng.random::<Scalar>() - 1.0;
+        let (sin, cos) = (Angle::FULL * rng.random::<Scalar>()).sin_cos();
+        let r = (1.0 - z*z).max(0.0).sqrt();
+
+        Self { x: r * cos, y: r * sin, z }
+    }
+
+    /// Random unit vector at most `half_angle` away from `axis`, uniformly distributed on that part of the sphere.
+    /// The axis doesn't have to be normalized.
+    #[cfg(feature = "rand")]
+    pub fn random_in_cone<R: rand::Rng + ?Sized>(axis: &Self, half_angle: Angle, rng: &mut R) -> Self
+    {
+        // Same as random_unit(), but with z only reaching down to cos(half_angle)
+        let min_z = half_angle.abs().min(Angle::HALF).cos();
+        let z = 1.0 - rng.random::<Scalar>() * (1.0 - min_z);
+        let (sin, cos) = (Angle::FULL * rng.random::<Scalar>()).sin_cos();
+        let r = (1.0 - z*z).max(0.0).sqrt();
+
+        let w = axis.normalize();
+        let u = w.any_perpendicular();
+        let v = w.cross(&u);
+
+        (r * cos) * u + (r * sin) * v + z * w
+    }
+
+    /// Distance between the points `self` and `other`.
+    pub fn distance_to(&self, other: &Self) -> Scalar
+    {
+        (*self - *other).norm()
+    }
+
+    /// Squared distance between the points `self` and `other`, cheaper than `distance_to()`.
+    pub fn distance_squared_to(&self, other: &Self) -> Scalar
+    {
+        (*self - *other).norm_squared()
+    }
+
     pub fn normalize(&self) -> Self
     {
         self * (1.0/self.norm())
     }
 
+    /// The components as slice `[x, y, z]`, e.g. to pass to functions taking `&[Scalar]`.
+    pub fn as_slice(&self) -> &[Scalar]
+    {
+        bytemuck::cast_ref::<Self, [Scalar; 3]>(self)
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [Scalar]
+    {
+        bytemuck::cast_mut::<Self, [Scalar; 3]>(self)
+    }
+
+    /// Component-wise minimum, e.g. the lower corner of a bounding box.
+    pub fn min(&self, other: &Self) -> Self
+    {
+        Self { x: self.x.min(other.x), y: self.y.min(other.y), z: self.z.min(other.z) }
+    }
+
+    /// Component-wise maximum, e.g. the upper corner of a bounding box.
+    pub fn max(&self, other: &Self) -> Self
+    {
+        Self { x: self.x.max(other.x), y: self.y.max(other.y), z: self.z.max(other.z) }
+    }
+
+    /// Clamp every component between the corresponding components of `min` and `max`.
+    pub fn clamp(&self, min: &Self, max: &Self) -> Self
+    {
+        Self { x: self.x.clamp(min.x, max.x), y: self.y.clamp(min.y, max.y), z: self.z.clamp(min.z, max.z) }
+    }
+
+    /// Component-wise absolute value.
+    pub fn abs(&self) -> Self
+    {
+        Self { x: self.x.abs(), y: self.y.abs(), z: self.z.abs() }
+    }
+
+    /// The smallest component.
+    pub fn min_element(&self) -> Scalar
+    {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// The largest component.
+    pub fn max_element(&self) -> Scalar
+    {
+        self.x.max(self.y).max(self.z)
+    }
+
+    /// Dot product, i.e. `|self| |other| cos(angle between them)`.
+    pub fn dot(&self, other: &Self) -> Scalar
+    {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// The part of this vector parallel to `axis`, which doesn't have to be normalized.
+    pub fn project_onto(&self, axis: &Self) -> Self
+    {
+        axis * (self.dot(axis) / axis.norm_squared())
+    }
+
+    /// The part of this vector perpendicular to `axis`, i.e. what's left after `project_onto()`.
+    pub fn reject_from(&self, axis: &Self) -> Self
+    {
+        *self - self.project_onto(axis)
+    }
+
+    /// The point on the line through `origin` in direction `dir` nearest to the point `self`.
+    pub fn project_onto_line(&self, origin: &Self, dir: &Self) -> Self
+    {
+        *origin + (*self - *origin).project_onto(dir)
+    }
+
+    /// Mirror this direction across the plane with the given `normal`, e.g. to bounce off a wall.
+    /// The normal doesn't have to be normalized.
+    pub fn reflect(&self, normal: &Self) -> Self
+    {
+        *self - 2.0 * self.project_onto(normal)
+    }
+
+    /// Mirror the point `self` across the plane through `plane_point` with the given `normal`.
+    pub fn reflect_across_plane(&self, plane_point: &Self, normal: &Self) -> Self
+    {
+        *self - 2.0 * (*self - *plane_point).project_onto(normal)
+    }
+
+    /// Unsigned angle between both vectors in [0°,180°].
+    /// Computed from both sine and cosine, so it stays accurate near 0° and 180°.
+    pub fn angle_between(&self, other: &Self) -> Angle
+    {
+        Angle::atan2(self.cross(other).norm(), self.dot(other))
+    }
+
+    /// Signed angle in (-180°,180°] rotating `self` towards `other` ccw around `axis`.
+    /// The vectors are projected onto the plane perpendicular to `axis` first.
+    pub fn signed_angle_between(&self, other: &Self, axis: &Self) -> Angle
+    {
+        let axis = axis.normalize();
+        let (a, b) = (self.reject_from(&axis), other.reject_from(&axis));
+
+        Angle::atan2(a.cross(&b).dot(&axis), a.dot(&b))
+    }
+
     pub fn cross(&self, other: &Self) -> Self
     {
         Self {
@@ -45,9 +321,65 @@ auto_ops::impl_op_ex_commutative!(* |lhs: &Vector3, rhs: &Scalar| -> Vector3 {
     }
 });
 
+auto_ops::impl_op_ex!(/ |lhs: &Vector3, rhs: &Scalar| -> Vector3 {
+    Vector3 {
+        x: lhs.x / rhs,
+        y: lhs.y / rhs,
+        z: lhs.z / rhs
+    }
+});
+auto_ops::impl_op_ex!(/= |lhs: &mut Vector3, rhs: &Scalar| {
+    lhs.x /= rhs;
+    lhs.y /= rhs;
+    lhs.z /= rhs;
+});
+
 impl From<Vector3> for [Scalar;3]
 {
     fn from(value: Vector3) -> Self {
         [value.x, value.y, value.z]
     }
 }
+
+impl From<Vector3> for (Scalar, Scalar, Scalar)
+{
+    fn from(value: Vector3) -> Self {
+        (value.x, value.y, value.z)
+    }
+}
+
+impl From<[Scalar;3]> for Vector3
+{
+    fn from(value: [Scalar;3]) -> Self {
+        Vector3 { x: value[0], y: value[1], z: value[2] }
+    }
+}
+
+/// Components by axis, i.e. 0 is x, 1 is y and 2 is z.
+impl core::ops::Index<usize> for Vector3
+{
+    type Output = Scalar;
+
+    fn index(&self, index: usize) -> &Scalar {
+        match index
+        {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("index out of bounds: Vector3 has 3 components but the index is {}", index),
+        }
+    }
+}
+
+impl core::ops::IndexMut<usize> for Vector3
+{
+    fn index_mut(&mut self, index: usize) -> &mut Scalar {
+        match index
+        {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("index out of bounds: Vector3 has 3 components but the index is {}", index),
+        }
+    }
+}